@@ -0,0 +1,171 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use dinosaur_game::{
+    app::{AppType, Game},
+    GameScreen, RenderToImageConfig,
+};
+use image::{Rgb, RgbImage};
+
+/// Set this environment variable to rewrite the reference images instead of comparing with them
+pub const UPDATE_GOLDEN_ENV: &str = "DINO_UPDATE_GOLDEN";
+
+/// Largest difference allowed per color channel before a pixel counts as changed
+pub const DEFAULT_TOLERANCE: u8 = 8;
+
+/// Directory holding the reference PNGs, one per [`GoldenScreen`]
+pub fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("golden")
+}
+
+/// A screen with a reference image and the route the game takes to reach it
+#[derive(Debug, Clone)]
+pub struct GoldenScreen {
+    pub screen: GameScreen,
+    pub route: Vec<GameScreen>,
+}
+
+impl GoldenScreen {
+    /// File name shared by the reference, the capture and the diff
+    pub fn name(&self) -> String {
+        format!("{:?}", self.screen)
+    }
+}
+
+/// Every screen covered by the golden suite
+pub fn golden_screens() -> Vec<GoldenScreen> {
+    use GameScreen::*;
+    // Pauses and game over are only reachable from a running game
+    [
+        (StartScreen, vec![StartScreen]),
+        (PlayScreen, vec![PlayScreen]),
+        (UnfocusedPauseScreen, vec![PlayScreen, UnfocusedPauseScreen]),
+        (ManuallyPauseScreen, vec![PlayScreen, ManuallyPauseScreen]),
+        (GameOverScreen, vec![PlayScreen, GameOverScreen]),
+    ]
+    .into_iter()
+    .map(|(screen, route)| GoldenScreen { screen, route })
+    .collect()
+}
+
+/// Run the game through `route` and write the final frame to `output`
+pub fn render_route(route: &[GameScreen], output: &Path) -> bool {
+    let _ = std::fs::remove_file(output);
+    let game = Game::init(AppType::RenderToImageTesting).with_render_to_image_config(
+        RenderToImageConfig {
+            screens: route.to_vec(),
+            output: output.to_path_buf(),
+            ..Default::default()
+        },
+    );
+    game.run().is_success() && output.exists()
+}
+
+#[derive(Debug)]
+pub enum GoldenError {
+    /// No reference image exists yet, rerun with [`UPDATE_GOLDEN_ENV`] set to create it
+    MissingReference(PathBuf),
+    /// The capture and the reference have different dimensions
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// Some pixels differ by more than the tolerance, see the diff image
+    PixelMismatch {
+        mismatched: u64,
+        total: u64,
+        diff: PathBuf,
+    },
+    Image(image::ImageError),
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenError::MissingReference(path) => write!(
+                f,
+                "missing reference {}, rerun with {UPDATE_GOLDEN_ENV}=1 to create it",
+                path.display()
+            ),
+            GoldenError::SizeMismatch { expected, actual } => write!(
+                f,
+                "size mismatch, expected {}x{} but got {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            GoldenError::PixelMismatch {
+                mismatched,
+                total,
+                diff,
+            } => write!(
+                f,
+                "{mismatched} of {total} pixels differ, diff written to {}",
+                diff.display()
+            ),
+            GoldenError::Image(e) => write!(f, "image error: {e}"),
+        }
+    }
+}
+
+impl From<image::ImageError> for GoldenError {
+    fn from(e: image::ImageError) -> Self {
+        GoldenError::Image(e)
+    }
+}
+
+/// Compare two images pixel by pixel.
+/// Returns the number of pixels off by more than `tolerance` on any channel,
+/// along with a diff image showing them in red over a faded copy of `actual`.
+pub fn diff_images(expected: &RgbImage, actual: &RgbImage, tolerance: u8) -> (u64, RgbImage) {
+    let mut mismatched = 0;
+    let diff = RgbImage::from_fn(actual.width(), actual.height(), |x, y| {
+        let a = actual.get_pixel(x, y);
+        let e = expected.get_pixel(x, y);
+        let changed = a.0.iter().zip(e.0).any(|(a, e)| a.abs_diff(e) > tolerance);
+        if changed {
+            mismatched += 1;
+            Rgb([255, 0, 0])
+        } else {
+            // Keep the surroundings visible without hiding the red pixels
+            Rgb(a.0.map(|c| 191 + c / 4))
+        }
+    });
+    (mismatched, diff)
+}
+
+/// Check the capture at `actual` against the reference image named `name`.
+/// On a mismatch the diff image is written next to the capture.
+pub fn check_golden(name: &str, actual: &Path, tolerance: u8) -> Result<(), GoldenError> {
+    let reference = golden_dir().join(format!("{name}.png"));
+    let actual_image = image::open(actual)?.to_rgb8();
+
+    if std::env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+        std::fs::create_dir_all(golden_dir()).map_err(image::ImageError::IoError)?;
+        actual_image.save(&reference)?;
+        return Ok(());
+    }
+
+    if !reference.exists() {
+        return Err(GoldenError::MissingReference(reference));
+    }
+    let expected_image = image::open(&reference)?.to_rgb8();
+    if expected_image.dimensions() != actual_image.dimensions() {
+        return Err(GoldenError::SizeMismatch {
+            expected: expected_image.dimensions(),
+            actual: actual_image.dimensions(),
+        });
+    }
+
+    let (mismatched, diff) = diff_images(&expected_image, &actual_image, tolerance);
+    if mismatched == 0 {
+        return Ok(());
+    }
+    let diff_path = actual.with_file_name(format!("{name}.diff.png"));
+    diff.save(&diff_path)?;
+    Err(GoldenError::PixelMismatch {
+        mismatched,
+        total: u64::from(actual_image.width()) * u64::from(actual_image.height()),
+        diff: diff_path,
+    })
+}
@@ -0,0 +1,89 @@
+use bevy::{
+    input::{
+        gamepad::{Gamepad, GamepadButton, RawGamepadButtonChangedEvent, RawGamepadEvent},
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::{Entity, KeyCode, NextState, State, Transform, With},
+};
+use dinosaur_game::{
+    app::Game,
+    components::{Dino, Obstacle},
+    GameScreen, GameStatus,
+};
+
+pub fn current_screen(game: &Game) -> GameScreen {
+    *game.world().resource::<State<GameScreen>>().get()
+}
+
+pub fn set_screen(game: &mut Game, screen: GameScreen) {
+    game.world_mut()
+        .resource_mut::<NextState<GameScreen>>()
+        .set(screen);
+}
+
+/// Leave the start screen and run the first tick of play
+pub fn start_run(game: &mut Game) {
+    game.update();
+    set_screen(game, GameScreen::PlayScreen);
+    game.update();
+}
+
+pub fn score(game: &Game) -> u64 {
+    game.world().resource::<GameStatus>().score
+}
+
+/// Without a dino nothing can be hit, so the run goes on forever
+pub fn remove_dinos(game: &mut Game) {
+    let world = game.world_mut();
+    let dinos: Vec<Entity> = world
+        .query_filtered::<Entity, With<Dino>>()
+        .iter(world)
+        .collect();
+    for dino in dinos {
+        world.despawn(dino);
+    }
+}
+
+/// X positions of every obstacle, left to right
+pub fn obstacle_positions(game: &mut Game) -> Vec<f32> {
+    let world = game.world_mut();
+    let mut positions: Vec<f32> = world
+        .query_filtered::<&Transform, With<Obstacle>>()
+        .iter(world)
+        .map(|t| t.translation.x)
+        .collect();
+    positions.sort_by(f32::total_cmp);
+    positions
+}
+
+/// Send a key event, picked up by the input systems on the next update
+pub fn send_key(game: &mut Game, key_code: KeyCode, state: ButtonState) {
+    game.world_mut().write_message(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(bevy::input::keyboard::NativeKey::Unidentified),
+        state,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+/// Spawn a connected gamepad, like the gamepad backend does when one is plugged in
+pub fn connect_gamepad(game: &mut Game) -> Entity {
+    game.world_mut().spawn(Gamepad::default()).id()
+}
+
+/// Send a gamepad button event, picked up by the input systems on the next update
+pub fn send_gamepad_button(
+    game: &mut Game,
+    gamepad: Entity,
+    button: GamepadButton,
+    state: ButtonState,
+) {
+    let value = if state.is_pressed() { 1.0 } else { 0.0 };
+    game.world_mut()
+        .write_message(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            gamepad, button, value,
+        )));
+}
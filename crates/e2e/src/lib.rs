@@ -1 +1,5 @@
+mod golden;
+mod headless;
 
+pub use golden::*;
+pub use headless::*;
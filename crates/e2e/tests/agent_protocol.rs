@@ -0,0 +1,237 @@
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, Read, Write},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+use bevy::app::AppExit;
+use dinosaur_game::{
+    app::{AppType, Game},
+    AgentAction, AgentCommand, AgentFrame, AgentIo, Observation,
+};
+
+/// Long enough for the first obstacle to reach an idle dino several times over
+const MAX_TICKS: u64 = 10_000;
+
+/// Reads what the other end sent, the end of the input once it hung up
+struct ChannelReader {
+    receiver: Mutex<Receiver<Vec<u8>>>,
+    unread: VecDeque<u8>,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.unread.is_empty() {
+            match self.receiver.lock().unwrap().recv() {
+                Ok(bytes) => self.unread.extend(bytes),
+                Err(_) => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.unread.len());
+        for (byte, unread) in buf.iter_mut().zip(self.unread.drain(..n)) {
+            *byte = unread;
+        }
+        Ok(n)
+    }
+}
+
+struct ChannelWriter(Sender<Vec<u8>>);
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .send(buf.to_vec())
+            .map_err(|_| std::io::ErrorKind::BrokenPipe)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn pipe() -> (ChannelWriter, BufReader<ChannelReader>) {
+    let (sender, receiver) = channel();
+    let reader = ChannelReader {
+        receiver: Mutex::new(receiver),
+        unread: VecDeque::new(),
+    };
+    (ChannelWriter(sender), BufReader::new(reader))
+}
+
+/// The agent's end of the protocol, with the game on its own thread as if it were a subprocess
+struct Agent {
+    commands: Option<ChannelWriter>,
+    frames: BufReader<ChannelReader>,
+    game: JoinHandle<AppExit>,
+}
+
+impl Agent {
+    fn start(seed: u64) -> Self {
+        let (commands, game_commands) = pipe();
+        let (game_frames, frames) = pipe();
+        let game = thread::spawn(move || {
+            let mut game = Game::init(AppType::Headless)
+                .with_seed(seed)
+                .with_agent_io(AgentIo::new(game_commands, game_frames));
+            loop {
+                game.update();
+                if let Some(exit) = game.should_exit() {
+                    return exit;
+                }
+            }
+        });
+        Self {
+            commands: Some(commands),
+            frames,
+            game,
+        }
+    }
+
+    fn frame(&mut self) -> AgentFrame {
+        let mut line = String::new();
+        self.frames.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    fn send(&mut self, line: &str) {
+        let commands = self.commands.as_mut().unwrap();
+        writeln!(commands, "{}", line).unwrap();
+    }
+
+    /// Idle until the run ends, returning the game over frame
+    fn run_until_game_over(&mut self) -> AgentFrame {
+        for tick in 0..MAX_TICKS {
+            let frame = self.frame();
+            if frame.done {
+                return frame;
+            }
+            assert_eq!(frame.tick, tick);
+            self.send(r#"{"action":"run"}"#);
+        }
+        panic!("run never ended");
+    }
+
+    fn exit(self) -> AppExit {
+        self.game.join().unwrap()
+    }
+}
+
+#[test]
+fn agent_commands_parse_from_their_documented_lines() {
+    let parse = |line: &str| serde_json::from_str::<AgentCommand>(line).unwrap();
+    assert_eq!(
+        parse(r#"{"action":"jump"}"#),
+        AgentCommand::Action(AgentAction::Jump)
+    );
+    assert_eq!(
+        parse(r#"{"action":"duck"}"#),
+        AgentCommand::Action(AgentAction::Duck)
+    );
+    assert_eq!(
+        parse(r#"{"action":"run"}"#),
+        AgentCommand::Action(AgentAction::Run)
+    );
+    assert_eq!(parse(r#"{"reset":42}"#), AgentCommand::Reset(Some(42)));
+    assert_eq!(parse(r#"{"reset":null}"#), AgentCommand::Reset(None));
+    assert_eq!(parse(r#""quit""#), AgentCommand::Quit);
+    assert!(serde_json::from_str::<AgentCommand>(r#"{"action":"fly"}"#).is_err());
+}
+
+#[test]
+fn agent_frames_are_single_lines() {
+    let frame = AgentFrame {
+        tick: 3,
+        score: 12,
+        done: false,
+        observation: Observation {
+            obstacle_ahead: true,
+            obstacle_distance: 120.0,
+            ..Default::default()
+        },
+    };
+    let line = serde_json::to_string(&frame).unwrap();
+    assert!(!line.contains('\n'));
+    let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(value["tick"], 3);
+    assert_eq!(value["done"], false);
+    assert_eq!(value["observation"]["obstacle_distance"], 120.0);
+}
+
+#[test]
+fn agent_session_plays_resets_and_quits() {
+    let mut agent = Agent::start(1);
+    let first = agent.run_until_game_over();
+    assert!(first.tick > 0);
+    assert!(first.observation.obstacle_ahead);
+
+    // The same seed again is the same run
+    agent.send(r#"{"reset":1}"#);
+    assert_eq!(agent.run_until_game_over(), first);
+
+    agent.send(r#"{"reset":1}"#);
+    let frame = agent.frame();
+    assert!(!frame.done);
+    assert_eq!(frame.tick, 0);
+    // Lines that aren't commands are skipped, the game waits for the next one
+    agent.send("not a command");
+    agent.send(r#"{"action":"jump"}"#);
+    let frame = agent.frame();
+    assert_eq!(frame.tick, 1);
+    assert!(frame.observation.dino_in_air);
+
+    agent.send(r#""quit""#);
+    assert_eq!(agent.exit(), AppExit::Success);
+}
+
+#[test]
+fn agent_hanging_up_quits() {
+    let mut agent = Agent::start(1);
+    assert_eq!(agent.frame().tick, 0);
+    agent.commands = None;
+    assert_eq!(agent.exit(), AppExit::Success);
+}
+
+#[test]
+fn reset_from_the_middle_of_a_run_starts_over() {
+    let mut agent = Agent::start(2);
+    for _ in 0..10 {
+        agent.frame();
+        agent.send(r#"{"action":"run"}"#);
+    }
+    assert_eq!(agent.frame().tick, 10);
+    agent.send(r#"{"reset":2}"#);
+    let frame = agent.frame();
+    assert!(!frame.done);
+    assert_eq!(frame.tick, 0);
+    agent.send(r#""quit""#);
+    assert_eq!(agent.exit(), AppExit::Success);
+}
+
+#[test]
+fn malformed_commands_do_not_stall_the_session() {
+    let mut agent = Agent::start(3);
+    for (tick, line) in [
+        "",
+        "not a command",
+        r#"{"action":"fly"}"#,
+        r#"{"action":"jump""#,
+        r#"{"reset":"soon"}"#,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let frame = agent.frame();
+        assert!(!frame.done);
+        assert_eq!(frame.tick, tick as u64);
+        agent.send(line);
+        agent.send(r#"{"action":"run"}"#);
+    }
+    assert_eq!(agent.frame().tick, 5);
+    agent.send(r#""quit""#);
+    assert_eq!(agent.exit(), AppExit::Success);
+}
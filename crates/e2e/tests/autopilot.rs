@@ -0,0 +1,71 @@
+use dinosaur_e2e::current_screen;
+use dinosaur_game::{
+    app::{AppType, Game},
+    GameScreen, GameStatus, RunProgress, SpeedControlInfo,
+};
+
+/// About ten minutes of play at the default fixed timestep
+const SOAK_TICKS: u32 = 40_000;
+
+#[test]
+fn autopilot_starts_playing_from_the_start_screen() {
+    let mut game = Game::init(AppType::Headless).with_autopilot();
+    game.update();
+    game.update();
+    assert_eq!(current_screen(&game), GameScreen::PlayScreen);
+}
+
+#[test]
+fn autopilot_gets_past_obstacles() {
+    let mut game = Game::init(AppType::Headless).with_seed(1).with_autopilot();
+    let mut most_cleared = 0;
+    for _ in 0..SOAK_TICKS / 4 {
+        game.update();
+        let cleared = game.world().resource::<RunProgress>().obstacles_cleared;
+        most_cleared = most_cleared.max(cleared);
+    }
+    // An idle dino never gets past the first one
+    assert!(most_cleared >= 3, "cleared {} obstacles", most_cleared);
+}
+
+/// Speed ramp a couple of clears short of its cap. From the start a run needs more than fifty
+/// clears to get there, the ramp doubling its step each time.
+const NEARLY_SATURATED: SpeedControlInfo = SpeedControlInfo {
+    speed_increment: u64::MAX / 8,
+    max_game_speed: u64::MAX - u64::MAX / 8,
+};
+
+/// Runs as long as the autopilot keeps going, every run starting near the top of the speed
+/// ramp. Once the ramp saturates the speed has to stay put instead of overflowing.
+#[test]
+fn autopilot_soak_keeps_playing() {
+    let mut game = Game::init(AppType::Headless).with_seed(2).with_autopilot();
+    let mut runs = 0;
+    let mut most_cleared = 0;
+    let mut screen = current_screen(&game);
+    for _ in 0..SOAK_TICKS {
+        game.update();
+        let next = current_screen(&game);
+        assert!(matches!(
+            next,
+            GameScreen::StartScreen | GameScreen::PlayScreen | GameScreen::GameOverScreen
+        ));
+        if next == GameScreen::PlayScreen && screen != GameScreen::PlayScreen {
+            runs += 1;
+            game.world_mut().insert_resource(NEARLY_SATURATED);
+        }
+        screen = next;
+
+        let cleared = game.world().resource::<RunProgress>().obstacles_cleared;
+        let speed = game.world().resource::<GameStatus>().speed;
+        let info = game.world().resource::<SpeedControlInfo>();
+        if next == GameScreen::PlayScreen && cleared >= 2 {
+            assert_eq!(speed, info.max_game_speed);
+            assert!(speed > 0);
+        }
+        most_cleared = most_cleared.max(cleared);
+    }
+    assert!(runs >= 1);
+    // Past the cap, with at least one more clear after the ramp saturated
+    assert!(most_cleared >= 3, "cleared {} obstacles", most_cleared);
+}
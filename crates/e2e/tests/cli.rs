@@ -0,0 +1,155 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use dinosaur_e2e::current_screen;
+use dinosaur_game::{
+    app::{AppType, Game},
+    cli::{CliArgs, HELP},
+    ConfigPath, GameConfig, GameScreen,
+};
+
+/// The game binary, cargo only builds it for tests of its own package so build it here
+fn game_binary() -> PathBuf {
+    let target_dir = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(Path::parent)
+        .unwrap()
+        .to_path_buf();
+    let mut build = Command::new(env!("CARGO"));
+    build.args(["build", "-p", "dinosaur-game", "--bin", "dinosaur-game"]);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    let status = build.status().unwrap();
+    assert!(status.success(), "failed to build the game binary");
+    target_dir.join(format!("dinosaur-game{}", std::env::consts::EXE_SUFFIX))
+}
+
+fn parse(args: &[&str]) -> Result<CliArgs, String> {
+    CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn no_arguments_change_nothing() {
+    assert_eq!(parse(&[]), Ok(CliArgs::default()));
+}
+
+#[test]
+fn every_flag_parses() {
+    let cli = parse(&[
+        "--config",
+        "settings/dino.json",
+        "--seed=42",
+        "--window-size",
+        "1280x720",
+        "--fullscreen",
+        "--screen=play",
+        "--mute",
+        "--debug",
+        "--replay",
+        "run.json",
+        "--policy=best_policy.json",
+        "--autopilot",
+        "--agent-stdio",
+    ])
+    .unwrap();
+    assert_eq!(
+        cli,
+        CliArgs {
+            help: false,
+            config: Some("settings/dino.json".into()),
+            seed: Some(42),
+            window_size: Some((1280.0, 720.0)),
+            fullscreen: true,
+            screen: Some(GameScreen::PlayScreen),
+            mute: true,
+            debug: true,
+            replay: Some("run.json".into()),
+            policy: Some("best_policy.json".into()),
+            autopilot: true,
+            agent_stdio: true,
+        }
+    );
+    assert!(parse(&["-h"]).unwrap().help);
+    assert!(parse(&["--help"]).unwrap().help);
+}
+
+#[test]
+fn bad_arguments_are_errors() {
+    assert!(parse(&["--jump-higher"]).is_err());
+    assert!(parse(&["--seed"]).is_err());
+    assert!(parse(&["--seed", "forty-two"]).is_err());
+    assert!(parse(&["--window-size", "1280"]).is_err());
+    assert!(parse(&["--window-size", "0x720"]).is_err());
+    assert!(parse(&["--screen", "game-over"]).is_err());
+    assert!(parse(&["--mute=no"]).is_err());
+}
+
+#[test]
+fn help_lists_every_flag() {
+    for flag in [
+        "--config",
+        "--seed",
+        "--window-size",
+        "--fullscreen",
+        "--screen",
+        "--mute",
+        "--debug",
+        "--replay",
+        "--policy",
+        "--autopilot",
+        "--agent-stdio",
+        "--help",
+    ] {
+        assert!(HELP.contains(flag), "{} missing from --help", flag);
+    }
+}
+
+#[test]
+fn config_file_is_loaded_from_the_given_path() {
+    let dir = std::env::temp_dir().join(format!("dino-cli-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("custom_config.json");
+    let config = GameConfig {
+        seed: Some(1234),
+        ..Default::default()
+    };
+    config.save_to_file(&path).unwrap();
+
+    let game = Game::init(AppType::Headless).with_config_file(&path);
+    assert_eq!(game.world().resource::<GameConfig>().seed, Some(1234));
+    assert_eq!(game.world().resource::<ConfigPath>().0, path);
+    assert_eq!(
+        game.world().resource::<ConfigPath>().bindings_file(),
+        dir.join("input_bindings.json")
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn start_screen_can_be_skipped() {
+    let mut game = Game::init(AppType::Headless).with_start_screen(GameScreen::PlayScreen);
+    game.update();
+    assert_eq!(current_screen(&game), GameScreen::PlayScreen);
+}
+
+#[test]
+fn unreadable_replay_exits_with_an_error() {
+    let path =
+        std::env::temp_dir().join(format!("dino-missing-replay-{}.json", std::process::id()));
+    let output = Command::new(game_binary())
+        .arg("--replay")
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with(&format!("error: cannot load --replay {}: ", path.display())),
+        "unexpected stderr: {}",
+        stderr
+    );
+}
@@ -0,0 +1,35 @@
+use dinosaur_e2e::{current_screen, start_run};
+use dinosaur_game::{
+    app::{AppType, Game},
+    GameConfig, GameScreen, GameStatus,
+};
+
+fn hits_within(game: &mut Game, ticks: u32) -> bool {
+    (0..ticks).any(|_| {
+        game.update();
+        current_screen(game) == GameScreen::GameOverScreen
+    })
+}
+
+#[test]
+fn fast_trees_cannot_skip_past_the_dino() {
+    let mut game = Game::init(AppType::Headless).with_seed(1);
+    // Obstacles move a third of the window width per second,
+    // so here they cover a hundred times the dino's width each tick
+    game.world_mut().resource_mut::<GameStatus>().window_width = 2_000_000.0;
+    start_run(&mut game);
+    // Enough for the first group to reach the dino, whatever the window width
+    assert!(hits_within(&mut game, 200));
+}
+
+#[test]
+fn only_hitboxes_collide() {
+    let mut game = Game::init(AppType::Headless).with_seed(1);
+    {
+        let mut config = game.world_mut().resource_mut::<GameConfig>();
+        config.dino_hitboxes.clear();
+        config.dino_duck_hitboxes.clear();
+    }
+    start_run(&mut game);
+    assert!(!hits_within(&mut game, 2_000));
+}
@@ -0,0 +1,169 @@
+use bevy::{
+    input::ButtonState,
+    prelude::{KeyCode, Sprite, Transform},
+};
+use dinosaur_e2e::{send_key, start_run};
+use dinosaur_game::{
+    app::{AppType, Game},
+    components::Dino,
+    GameConfig,
+};
+
+fn tick(game: &mut Game, ticks: usize) {
+    for _ in 0..ticks {
+        game.update();
+    }
+}
+
+/// Height above the ground of the dino's feet, its sprite height and whether it's airborne
+fn dino_pose(game: &mut Game) -> (f32, f32, bool) {
+    let ground = game.world().resource::<GameConfig>().ground_y_pos;
+    let world = game.world_mut();
+    let (transform, sprite, dino) = world
+        .query::<(&Transform, &Sprite, &Dino)>()
+        .single(world)
+        .unwrap();
+    let height = sprite.custom_size.unwrap().y;
+    (
+        transform.translation.y - height / 2.0 - ground,
+        height,
+        dino.in_air,
+    )
+}
+
+fn tap(game: &mut Game, key: KeyCode) {
+    send_key(game, key, ButtonState::Pressed);
+    game.update();
+    send_key(game, key, ButtonState::Released);
+}
+
+#[test]
+fn holding_down_ducks_the_dino() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    let config = game.world().resource::<GameConfig>().clone();
+
+    send_key(&mut game, KeyCode::ArrowDown, ButtonState::Pressed);
+    tick(&mut game, 2);
+    let (feet, height, _) = dino_pose(&mut game);
+    assert_eq!(height, config.dino_duck_height);
+    assert!(feet.abs() < 0.01);
+
+    send_key(&mut game, KeyCode::ArrowDown, ButtonState::Released);
+    tick(&mut game, 2);
+    let (feet, height, _) = dino_pose(&mut game);
+    assert_eq!(height, config.dino_height);
+    assert!(feet.abs() < 0.01);
+}
+
+#[test]
+fn ducking_mid_air_falls_fast() {
+    let mut plain = Game::init(AppType::Headless);
+    start_run(&mut plain);
+    let mut ducked = Game::init(AppType::Headless);
+    start_run(&mut ducked);
+    for game in [&mut plain, &mut ducked] {
+        tap(game, KeyCode::Space);
+        tick(game, 5);
+        assert!(dino_pose(game).2);
+    }
+
+    send_key(&mut ducked, KeyCode::KeyS, ButtonState::Pressed);
+    tick(&mut plain, 10);
+    tick(&mut ducked, 10);
+    assert!(dino_pose(&mut plain).2);
+    let (feet, height, in_air) = dino_pose(&mut ducked);
+    assert!(!in_air);
+    assert!(feet.abs() < 0.01);
+    assert_eq!(
+        height,
+        ducked.world().resource::<GameConfig>().dino_duck_height
+    );
+}
+
+/// Highest the dino's feet get during a jump with the key held for `hold_ticks`
+fn jump_peak(hold_ticks: usize) -> f32 {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    send_key(&mut game, KeyCode::Space, ButtonState::Pressed);
+    let mut peak: f32 = 0.0;
+    for tick in 0..120 {
+        if tick == hold_ticks.max(1) {
+            send_key(&mut game, KeyCode::Space, ButtonState::Released);
+        }
+        game.update();
+        let (feet, _, in_air) = dino_pose(&mut game);
+        peak = peak.max(feet);
+        if !in_air {
+            return peak;
+        }
+    }
+    panic!("dino never landed");
+}
+
+#[test]
+fn holding_jump_goes_higher() {
+    let tap = jump_peak(1);
+    let short_hold = jump_peak(4);
+    let long_hold = jump_peak(30);
+    assert!(tap > 0.0);
+    assert!(short_hold > tap);
+    assert!(long_hold > short_hold);
+    // Holding only counts for `dino_max_jump_hold`
+    assert_eq!(long_hold, jump_peak(60));
+}
+
+/// Ticks from a tap until the dino is back on the ground
+fn tap_jump_ticks() -> usize {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    tap(&mut game, KeyCode::Space);
+    (1..120)
+        .find(|_| {
+            game.update();
+            !dino_pose(&mut game).2
+        })
+        .expect("dino never landed")
+}
+
+/// Tap, tap again `early_ticks` before landing, and tell if the dino is jumping again after landing
+fn jumps_again_after_early_press(early_ticks: usize) -> bool {
+    let landing = tap_jump_ticks();
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    tap(&mut game, KeyCode::Space);
+    tick(&mut game, landing - early_ticks);
+    tap(&mut game, KeyCode::Space);
+    assert!(dino_pose(&mut game).2, "second press came after landing");
+    tick(&mut game, early_ticks + 2);
+    dino_pose(&mut game).2
+}
+
+#[test]
+fn jump_pressed_before_landing_is_buffered() {
+    // 3 ticks is about 47ms, inside the default 100ms buffer
+    assert!(jumps_again_after_early_press(3));
+    // 15 ticks is about 234ms, long gone by the time the dino lands
+    assert!(!jumps_again_after_early_press(15));
+}
+
+/// Put the dino in the air without a jump, wait `ticks` and press jump
+fn jumps_after_leaving_ground(ticks: usize) -> bool {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    let world = game.world_mut();
+    let mut dino = world.query::<&mut Dino>().single_mut(world).unwrap();
+    dino.in_air = true;
+    dino.altitude = 100.0;
+    tick(&mut game, ticks);
+    tap(&mut game, KeyCode::Space);
+    let world = game.world_mut();
+    world.query::<&Dino>().single(world).unwrap().is_jumping
+}
+
+#[test]
+fn coyote_time_allows_late_jumps() {
+    // 2 ticks is about 31ms, inside the default 80ms window
+    assert!(jumps_after_leaving_ground(2));
+    assert!(!jumps_after_leaving_ground(10));
+}
@@ -0,0 +1,69 @@
+use dinosaur_game::{AgentAction, DinoEnv, Observation};
+
+/// Long enough for the first obstacle to reach an idle dino several times over
+const MAX_STEPS: u32 = 10_000;
+
+/// Step with `policy` until the run is over, returning every observation and the total reward
+fn play(
+    env: &mut DinoEnv,
+    seed: u64,
+    policy: impl Fn(&Observation) -> AgentAction,
+) -> (Vec<Observation>, f32) {
+    let mut observation = env.reset(seed);
+    let mut observations = vec![observation];
+    let mut total = 0.0;
+    for _ in 0..MAX_STEPS {
+        let (next, reward, done) = env.step(policy(&observation));
+        observations.push(next);
+        total += reward;
+        if done {
+            return (observations, total);
+        }
+        observation = next;
+    }
+    panic!("run never ended");
+}
+
+#[test]
+fn idle_agent_runs_into_the_first_obstacle() {
+    let mut env = DinoEnv::default();
+    let (observations, total) = play(&mut env, 1, |_| AgentAction::Run);
+    assert!(total > 0.0);
+    assert!(observations[0].obstacle_ahead);
+    assert!(observations[1].obstacle_distance < observations[0].obstacle_distance);
+    assert!(observations.iter().all(|o| !o.dino_in_air));
+
+    let (observation, reward, done) = env.step(AgentAction::Jump);
+    assert!(done);
+    assert_eq!(reward, 0.0);
+    assert_eq!(observation, *observations.last().unwrap());
+}
+
+#[test]
+fn same_seed_same_episode() {
+    let jumper = |o: &Observation| {
+        if o.obstacle_ahead && o.obstacle_distance < 150.0 {
+            AgentAction::Jump
+        } else {
+            AgentAction::Run
+        }
+    };
+    let mut env = DinoEnv::default();
+    let first = play(&mut env, 7, jumper);
+    // Resetting throws the previous run away, whatever state it was left in
+    env.reset(3);
+    env.step(AgentAction::Jump);
+    assert_eq!(play(&mut env, 7, jumper), first);
+    assert_eq!(play(&mut DinoEnv::default(), 7, jumper), first);
+}
+
+#[test]
+fn jumping_shows_in_the_observation() {
+    let mut env = DinoEnv::default();
+    env.reset(1);
+    let (observation, _, done) = env.step(AgentAction::Jump);
+    assert!(!done);
+    assert!(observation.dino_in_air);
+    assert!(observation.dino_altitude > 0.0);
+    assert!(observation.dino_velocity > 0.0);
+}
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use dinosaur_e2e::{check_golden, diff_images, golden_screens, render_route, DEFAULT_TOLERANCE};
+use image::{Rgb, RgbImage};
+
+#[test]
+#[ignore = "needs a wgpu adapter, run with `./just.ts test-render`"]
+fn every_screen_matches_golden_image() {
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("golden");
+    std::fs::create_dir_all(&out_dir).unwrap();
+
+    let mut failures = Vec::new();
+    for golden in golden_screens() {
+        let name = golden.name();
+        let capture = out_dir.join(format!("{name}.png"));
+        if !render_route(&golden.route, &capture) {
+            failures.push(format!("{name}: failed to render"));
+            continue;
+        }
+        if let Err(e) = check_golden(&name, &capture, DEFAULT_TOLERANCE) {
+            failures.push(format!("{name}: {e}"));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn diff_only_flags_pixels_beyond_tolerance() {
+    let expected = RgbImage::from_pixel(4, 4, Rgb([255, 255, 255]));
+    let mut actual = expected.clone();
+    actual.put_pixel(0, 0, Rgb([250, 255, 255]));
+    actual.put_pixel(3, 3, Rgb([0, 0, 0]));
+
+    let (mismatched, diff) = diff_images(&expected, &actual, DEFAULT_TOLERANCE);
+    assert_eq!(mismatched, 1);
+    assert_eq!(*diff.get_pixel(3, 3), Rgb([255, 0, 0]));
+    assert_ne!(*diff.get_pixel(0, 0), Rgb([255, 0, 0]));
+}
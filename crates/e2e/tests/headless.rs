@@ -0,0 +1,158 @@
+use bevy::prelude::With;
+use dinosaur_e2e::{
+    current_screen, obstacle_positions, remove_dinos, score, set_screen, start_run,
+};
+use dinosaur_game::{
+    app::{AppType, Game},
+    components::Bird,
+    step_one_tick, GameConfig, GameRng, GameScreen, GameStatus, RunProgress,
+};
+
+/// Long enough for the first tree to reach an idle dino several times over
+const MAX_TICKS: u32 = 10_000;
+
+/// Step the game until the dino hits a tree, returning how many ticks it took
+fn play_until_game_over(game: &mut Game) -> Option<u32> {
+    for tick in 0..MAX_TICKS {
+        game.update();
+        if current_screen(game) == GameScreen::GameOverScreen {
+            return Some(tick);
+        }
+    }
+    None
+}
+
+/// Start a headless run and return the number of ticks until the dino hits a tree
+fn ticks_until_game_over() -> Option<u32> {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    play_until_game_over(&mut game)
+}
+
+#[test]
+fn idle_dino_hits_tree() {
+    assert!(ticks_until_game_over().is_some());
+}
+
+#[test]
+fn headless_runs_are_deterministic() {
+    let first = ticks_until_game_over();
+    for _ in 0..10 {
+        assert_eq!(ticks_until_game_over(), first);
+    }
+}
+
+#[test]
+fn score_counts_during_play_and_freezes_on_game_over() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    play_until_game_over(&mut game).unwrap();
+
+    let final_score = score(&game);
+    assert!(final_score > 0);
+    for _ in 0..100 {
+        game.update();
+    }
+    assert_eq!(score(&game), final_score);
+
+    set_screen(&mut game, GameScreen::PlayScreen);
+    game.update();
+    assert!(score(&game) < final_score);
+}
+
+#[test]
+fn pausing_keeps_the_run_going() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    for _ in 0..60 {
+        game.update();
+    }
+    let before_pause = score(&game);
+    assert!(before_pause > 0);
+
+    set_screen(&mut game, GameScreen::ManuallyPauseScreen);
+    for _ in 0..60 {
+        game.update();
+    }
+    assert_eq!(score(&game), before_pause);
+
+    set_screen(&mut game, GameScreen::PlayScreen);
+    game.update();
+    assert!(score(&game) >= before_pause);
+}
+
+#[test]
+fn stepping_plays_single_ticks_while_paused() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    set_screen(&mut game, GameScreen::ManuallyPauseScreen);
+    game.update();
+    let paused_at = game.world().resource::<RunProgress>().distance;
+
+    step_one_tick(game.world_mut());
+    let stepped_to = game.world().resource::<RunProgress>().distance;
+    assert!(stepped_to > paused_at);
+    game.update();
+    assert_eq!(game.world().resource::<RunProgress>().distance, stepped_to);
+    assert_eq!(current_screen(&game), GameScreen::ManuallyPauseScreen);
+}
+
+#[test]
+fn obstacles_keep_coming_and_despawn_off_screen() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+
+    remove_dinos(&mut game);
+    for _ in 0..3_000 {
+        game.update();
+    }
+
+    assert_eq!(current_screen(&game), GameScreen::PlayScreen);
+    assert!(game.world().resource::<RunProgress>().obstacles_cleared > 5);
+    let left_edge = -game.world().resource::<GameStatus>().window_width / 2.0;
+    let obstacles = obstacle_positions(&mut game);
+    assert!(!obstacles.is_empty() && obstacles.len() < 20);
+    assert!(obstacles.iter().all(|&x| x > left_edge - 100.0));
+}
+
+/// Obstacles on screen after a while of a dino-less run started from `seed`
+fn obstacle_layout(seed: u64) -> Vec<f32> {
+    let mut game = Game::init(AppType::Headless).with_seed(seed);
+    start_run(&mut game);
+    remove_dinos(&mut game);
+    for _ in 0..1_000 {
+        game.update();
+    }
+    assert_eq!(game.world().resource::<GameRng>().seed(), seed);
+    obstacle_positions(&mut game)
+}
+
+#[test]
+fn same_seed_spawns_same_obstacles() {
+    assert_eq!(obstacle_layout(42), obstacle_layout(42));
+    assert_ne!(obstacle_layout(42), obstacle_layout(7));
+}
+
+/// Birds seen during a dino-less run with the given unlock speed
+fn birds_spawned(bird_unlock_speed: u64) -> usize {
+    let mut game = Game::init(AppType::Headless).with_seed(1);
+    game.world_mut()
+        .resource_mut::<GameConfig>()
+        .bird_unlock_speed = bird_unlock_speed;
+    start_run(&mut game);
+    remove_dinos(&mut game);
+
+    let mut birds = 0;
+    for _ in 0..3_000 {
+        game.update();
+        let world = game.world_mut();
+        birds = birds.max(world.query_filtered::<(), With<Bird>>().iter(world).count());
+    }
+    birds
+}
+
+#[test]
+fn birds_only_fly_once_unlocked() {
+    assert_eq!(birds_spawned(u64::MAX), 0);
+    assert!(birds_spawned(0) > 0);
+}
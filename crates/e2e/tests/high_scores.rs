@@ -0,0 +1,37 @@
+use dinosaur_game::{HighScoreEntry, HighScores};
+
+fn entry(score: u64) -> HighScoreEntry {
+    HighScoreEntry {
+        score,
+        max_speed: 5,
+        duration_secs: 1.0,
+        timestamp: 0,
+    }
+}
+
+#[test]
+fn table_keeps_best_runs_in_order() {
+    let mut high_scores = HighScores::default();
+    for score in 1..=HighScores::MAX_ENTRIES as u64 {
+        high_scores.insert(entry(score * 10));
+    }
+    assert_eq!(high_scores.insert(entry(5)), None);
+    assert_eq!(high_scores.insert(entry(55)), Some(5));
+    assert_eq!(high_scores.entries.len(), HighScores::MAX_ENTRIES);
+    assert_eq!(high_scores.entries[0].score, 100);
+    assert_eq!(high_scores.entries.last().unwrap().score, 20);
+
+    let json = high_scores.to_json().unwrap();
+    assert_eq!(
+        HighScores::from_json(&json).unwrap().entries,
+        high_scores.entries
+    );
+}
+
+#[test]
+fn entry_date_is_utc_calendar_day() {
+    let mut run = entry(1);
+    assert_eq!(run.date(), "1970-01-01");
+    run.timestamp = 951_782_400; // leap day
+    assert_eq!(run.date(), "2000-02-29");
+}
@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::message::Messages,
+    input::{
+        gamepad::{GamepadButton, GamepadRumbleRequest},
+        ButtonState,
+    },
+    prelude::KeyCode,
+    time::TimeUpdateStrategy,
+};
+use dinosaur_e2e::{
+    connect_gamepad, current_screen, send_gamepad_button, send_key, set_screen, start_run,
+};
+use dinosaur_game::{
+    app::{AppType, Game},
+    components::Dino,
+    ActionState, GameAction, GameScreen, InputBinding, InputBindings,
+};
+
+fn dino_in_air(game: &mut Game) -> bool {
+    let world = game.world_mut();
+    world.query::<&Dino>().single(world).unwrap().in_air
+}
+
+#[test]
+fn bindings_round_trip_through_json() {
+    let mut bindings = InputBindings::default();
+    bindings.bind(GameAction::Jump, InputBinding::Key(KeyCode::KeyX));
+    bindings.unbind(GameAction::Jump, InputBinding::Touch);
+
+    let json = bindings.to_json().unwrap();
+    assert_eq!(InputBindings::from_json(&json).unwrap(), bindings);
+}
+
+#[test]
+fn rebound_key_drives_the_action() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    {
+        let mut bindings = game.world_mut().resource_mut::<InputBindings>();
+        bindings.unbind(GameAction::Jump, InputBinding::Key(KeyCode::Space));
+        bindings.bind(GameAction::Jump, InputBinding::Key(KeyCode::KeyX));
+    }
+
+    send_key(&mut game, KeyCode::Space, ButtonState::Pressed);
+    game.update();
+    assert!(!dino_in_air(&mut game));
+    send_key(&mut game, KeyCode::Space, ButtonState::Released);
+
+    send_key(&mut game, KeyCode::KeyX, ButtonState::Pressed);
+    game.update();
+    assert!(dino_in_air(&mut game));
+}
+
+#[test]
+fn tap_within_one_frame_is_not_lost() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    send_key(&mut game, KeyCode::Space, ButtonState::Pressed);
+    send_key(&mut game, KeyCode::Space, ButtonState::Released);
+    game.update();
+
+    let actions = game.world().resource::<ActionState>();
+    assert!(actions.just_pressed(GameAction::Jump));
+    assert!(dino_in_air(&mut game));
+
+    game.update();
+    let actions = game.world().resource::<ActionState>();
+    assert!(actions.just_released(GameAction::Jump));
+}
+
+#[test]
+fn press_that_starts_the_run_does_not_jump() {
+    let mut game = Game::init(AppType::Headless);
+    game.update();
+    // Frames faster than the simulation, no tick runs between the press and the run starting
+    game.world_mut()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    send_key(&mut game, KeyCode::Space, ButtonState::Pressed);
+    game.update();
+    send_key(&mut game, KeyCode::Space, ButtonState::Released);
+    set_screen(&mut game, GameScreen::PlayScreen);
+    game.update();
+
+    game.world_mut()
+        .insert_resource(TimeUpdateStrategy::FixedTimesteps(1));
+    for _ in 0..5 {
+        game.update();
+        assert!(!dino_in_air(&mut game));
+    }
+}
+
+#[test]
+fn gamepad_drives_jump_and_duck() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    let gamepad = connect_gamepad(&mut game);
+    game.update();
+
+    send_gamepad_button(
+        &mut game,
+        gamepad,
+        GamepadButton::DPadDown,
+        ButtonState::Pressed,
+    );
+    game.update();
+    assert!(game
+        .world()
+        .resource::<ActionState>()
+        .pressed(GameAction::Duck));
+    send_gamepad_button(
+        &mut game,
+        gamepad,
+        GamepadButton::DPadDown,
+        ButtonState::Released,
+    );
+    game.update();
+
+    send_gamepad_button(
+        &mut game,
+        gamepad,
+        GamepadButton::South,
+        ButtonState::Pressed,
+    );
+    game.update();
+    assert!(dino_in_air(&mut game));
+}
+
+#[test]
+fn gamepad_start_both_pauses_and_confirms() {
+    let mut game = Game::init(AppType::Headless);
+    let gamepad = connect_gamepad(&mut game);
+    game.update();
+
+    send_gamepad_button(
+        &mut game,
+        gamepad,
+        GamepadButton::Start,
+        ButtonState::Pressed,
+    );
+    game.update();
+    let actions = game.world().resource::<ActionState>();
+    assert!(actions.just_pressed(GameAction::Pause));
+    assert!(actions.just_pressed(GameAction::Confirm));
+}
+
+#[test]
+fn crashing_rumbles_gamepads() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    let gamepad = connect_gamepad(&mut game);
+    while current_screen(&game) != GameScreen::GameOverScreen {
+        game.update();
+    }
+
+    let rumbles = game.world().resource::<Messages<GamepadRumbleRequest>>();
+    assert!(rumbles.iter_current_update_messages().any(
+        |request| matches!(request, GamepadRumbleRequest::Add { gamepad: g, .. } if *g == gamepad)
+    ));
+}
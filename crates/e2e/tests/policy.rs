@@ -0,0 +1,55 @@
+use dinosaur_e2e::{current_screen, start_run};
+use dinosaur_game::{
+    app::{AppType, Game},
+    components::Dino,
+    AgentAction, DinoEnv, GameScreen, NeuralPolicy, Observation,
+};
+
+#[test]
+fn blank_policy_just_runs() {
+    let policy = NeuralPolicy::zeroed(4);
+    let observation = Observation {
+        obstacle_ahead: true,
+        obstacle_distance: 10.0,
+        ..Default::default()
+    };
+    assert_eq!(policy.act(&observation), AgentAction::Run);
+}
+
+#[test]
+fn policy_files_round_trip_and_reject_the_wrong_size() {
+    let mut policy = NeuralPolicy::zeroed(3);
+    policy.weights[0] = 0.5;
+    let loaded = NeuralPolicy::from_json(&policy.to_json().unwrap()).unwrap();
+    assert_eq!(loaded, policy);
+
+    policy.weights.pop();
+    assert!(NeuralPolicy::from_json(&policy.to_json().unwrap()).is_err());
+}
+
+/// Output bias on jump only, so this network jumps whatever it sees
+fn always_jump() -> NeuralPolicy {
+    let hidden = 2;
+    let mut policy = NeuralPolicy::zeroed(hidden);
+    let jump_bias = NeuralPolicy::weight_count(hidden) - 1 - (hidden + 1);
+    policy.weights[jump_bias] = 1.0;
+    policy
+}
+
+#[test]
+fn game_plays_a_loaded_policy() {
+    let policy = always_jump();
+    let mut env = DinoEnv::default();
+    let observation = env.reset(1);
+    assert_eq!(policy.act(&observation), AgentAction::Jump);
+
+    let mut game = Game::init(AppType::Headless).with_policy(policy);
+    start_run(&mut game);
+    let jumped = (0..60).any(|_| {
+        game.update();
+        let world = game.world_mut();
+        world.query::<&Dino>().iter(world).any(|dino| dino.in_air)
+    });
+    assert!(jumped);
+    assert_eq!(current_screen(&game), GameScreen::PlayScreen);
+}
@@ -1,6 +1,33 @@
+use std::path::PathBuf;
+
+use dinosaur_game::{
+    app::{AppType, Game},
+    GameScreen, RenderToImageConfig,
+};
+
 #[test]
+#[ignore = "needs a wgpu adapter, run with `./just.ts test-render`"]
 fn render_game_to_image() {
-    //let test_game = Game::init(AppType::RenderToImageTesting);
-    //let exit = test_game.run();
-    //assert!(exit.is_success());
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    for screen in [
+        GameScreen::StartScreen,
+        GameScreen::PlayScreen,
+        GameScreen::GameOverScreen,
+    ] {
+        let output = out_dir.join(format!("{screen:?}.png"));
+        let _ = std::fs::remove_file(&output);
+
+        let test_game = Game::init(AppType::RenderToImageTesting).with_render_to_image_config(
+            RenderToImageConfig {
+                screens: vec![screen],
+                output: output.clone(),
+                ..Default::default()
+            },
+        );
+        let exit = test_game.run();
+        assert!(exit.is_success());
+
+        let metadata = std::fs::metadata(&output).expect("frame should be written to disk");
+        assert!(metadata.len() > 0, "{} is empty", output.display());
+    }
 }
@@ -0,0 +1,161 @@
+use bevy::{
+    input::ButtonState,
+    prelude::{Entity, Fixed, KeyCode, Time, With, Without},
+    time::TimeUpdateStrategy,
+};
+use dinosaur_e2e::{current_screen, score, send_key, set_screen, start_run};
+use dinosaur_game::{
+    app::{AppType, Game},
+    components::Dino,
+    BestReplay, GameConfig, GameRng, GameScreen, GameStatus, Ghost, Replay, ReplayRecorder,
+    RunProgress,
+};
+
+/// Long enough for a run with a few jumps to end
+const MAX_TICKS: u32 = 10_000;
+
+/// Play until game over, tapping jump every `jump_every` ticks.
+/// Returns the ticks it took and the final score.
+fn play(game: &mut Game, jump_every: Option<u32>) -> (u32, u64) {
+    start_run(game);
+    for tick in 0..MAX_TICKS {
+        if let Some(every) = jump_every {
+            match tick % every {
+                0 => send_key(game, KeyCode::Space, ButtonState::Pressed),
+                5 => send_key(game, KeyCode::Space, ButtonState::Released),
+                _ => {}
+            }
+        }
+        game.update();
+        if current_screen(game) == GameScreen::GameOverScreen {
+            return (tick, score(game));
+        }
+    }
+    panic!("run never ended");
+}
+
+#[test]
+fn replay_reproduces_the_recorded_run() {
+    let mut game = Game::init(AppType::Headless).with_seed(3);
+    let recorded = play(&mut game, Some(45));
+    let replay = game.world().resource::<ReplayRecorder>().replay().clone();
+    assert_eq!(replay.seed, 3);
+    assert!(!replay.events.is_empty());
+    // The jumps have to matter for the replay to prove anything
+    let mut idle = Game::init(AppType::Headless).with_seed(3);
+    assert_ne!(play(&mut idle, None), recorded);
+
+    let replay = Replay::from_json(&replay.to_json().unwrap()).unwrap();
+    let mut game = Game::init(AppType::Headless).with_replay(replay);
+    assert_eq!(play(&mut game, None), recorded);
+}
+
+#[test]
+fn replays_from_other_versions_are_rejected() {
+    let mut game = Game::init(AppType::Headless);
+    play(&mut game, None);
+    let mut replay = game.world().resource::<ReplayRecorder>().replay().clone();
+    replay.version = Replay::VERSION + 1;
+    assert!(Replay::from_json(&replay.to_json().unwrap()).is_err());
+}
+
+/// Altitude of every dino, ghosts included, sorted
+fn altitudes(game: &mut Game) -> Vec<f32> {
+    let world = game.world_mut();
+    let mut altitudes: Vec<f32> = world
+        .query::<&Dino>()
+        .iter(world)
+        .map(|dino| dino.altitude)
+        .collect();
+    altitudes.sort_by(f32::total_cmp);
+    altitudes
+}
+
+#[test]
+fn ghost_retraces_the_best_run() {
+    let mut game = Game::init(AppType::Headless);
+    start_run(&mut game);
+    let mut recorded = Vec::new();
+    for tick in 0..MAX_TICKS {
+        match tick % 45 {
+            0 => send_key(&mut game, KeyCode::Space, ButtonState::Pressed),
+            5 => send_key(&mut game, KeyCode::Space, ButtonState::Released),
+            _ => {}
+        }
+        game.update();
+        if current_screen(&game) == GameScreen::GameOverScreen {
+            break;
+        }
+        recorded.extend(altitudes(&mut game));
+    }
+    let best = game.world().resource::<BestReplay>().0.clone().unwrap();
+    assert_eq!(best.score, score(&game));
+
+    game.world_mut().resource_mut::<GameConfig>().ghost = true;
+    set_screen(&mut game, GameScreen::PlayScreen);
+    game.update();
+    assert_eq!(game.world().resource::<GameRng>().seed(), best.seed);
+    // Without the live dino only the ghost is left, and it never hits anything
+    let world = game.world_mut();
+    let live: Vec<Entity> = world
+        .query_filtered::<Entity, (With<Dino>, Without<Ghost>)>()
+        .iter(world)
+        .collect();
+    for dino in live {
+        world.despawn(dino);
+    }
+
+    // The recording starts a tick into the run, after the one `start_run` plays
+    let mut ghost = Vec::new();
+    for _ in 0..recorded.len() {
+        game.update();
+        ghost.extend(altitudes(&mut game));
+    }
+    assert_eq!(ghost, recorded);
+    assert_eq!(current_screen(&game), GameScreen::PlayScreen);
+    game.update();
+    game.update();
+    assert!(altitudes(&mut game).is_empty());
+}
+
+#[test]
+fn replay_plays_the_same_at_any_frame_rate() {
+    let mut game = Game::init(AppType::Headless).with_seed(9);
+    let (_, recorded_score) = play(&mut game, Some(40));
+    let recorded_distance = game.world().resource::<RunProgress>().distance;
+    let replay = game.world().resource::<ReplayRecorder>().replay().clone();
+
+    // Three ticks every frame instead of one
+    let mut game = Game::init(AppType::Headless).with_replay(replay);
+    let frame = game
+        .world()
+        .resource::<Time<Fixed>>()
+        .timestep()
+        .mul_f32(3.0);
+    game.world_mut()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(frame));
+    let (_, replayed_score) = play(&mut game, None);
+    assert_eq!(replayed_score, recorded_score);
+    assert_eq!(
+        game.world().resource::<RunProgress>().distance,
+        recorded_distance
+    );
+}
+
+#[test]
+fn replay_plays_the_same_in_any_window() {
+    let mut game = Game::init(AppType::Headless).with_seed(4);
+    let recorded = play(&mut game, Some(45));
+    let replay = game.world().resource::<ReplayRecorder>().replay().clone();
+    let recorded_width = game.world().resource::<GameStatus>().window_width;
+    assert_eq!(replay.window_width, recorded_width);
+
+    // Obstacles would move at half the speed in a window half as wide
+    let mut game = Game::init(AppType::Headless).with_replay(replay);
+    game.world_mut().resource_mut::<GameStatus>().window_width = recorded_width / 2.0;
+    assert_eq!(play(&mut game, None), recorded);
+    assert_eq!(
+        game.world().resource::<GameStatus>().window_width,
+        recorded_width
+    );
+}
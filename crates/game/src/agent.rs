@@ -0,0 +1,131 @@
+//! Let a program play: it sees an [`Observation`] of every tick and answers with an [`AgentAction`].
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{Dino, Obstacle},
+    game_logic::sprite_size,
+    ghost::Ghost,
+    obstacle::world_speed,
+    replay::record_actions,
+    FixedActionState, GameAction, GameConfig, GameStatus, SimulationSystems,
+};
+
+/// What an agent can do during a tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentAction {
+    /// Keep running, letting go of jump and duck
+    #[default]
+    Run,
+    Jump,
+    Duck,
+}
+
+/// Action an agent holds for the next ticks.
+/// While present it replaces the live jump and duck inputs.
+#[derive(Debug, Default, Clone, Copy, Resource)]
+pub struct AgentInput(pub AgentAction);
+
+/// What an agent gets to see of the game, measured in pixels and seconds
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Observation {
+    /// Whether an obstacle is coming at all, the obstacle fields are zero otherwise
+    pub obstacle_ahead: bool,
+    /// From the dino's front to the front of the next obstacle, zero once they overlap
+    pub obstacle_distance: f32,
+    pub obstacle_width: f32,
+    pub obstacle_height: f32,
+    /// Height of the obstacle's underside above the ground, only birds fly
+    pub obstacle_altitude: f32,
+    /// Height of the dino's feet above the ground
+    pub dino_altitude: f32,
+    /// Vertical speed of the dino, positive is up
+    pub dino_velocity: f32,
+    pub dino_in_air: bool,
+    pub dino_ducking: bool,
+    /// How fast obstacles come at the dino, per second
+    pub world_speed: f32,
+}
+
+impl Observation {
+    /// Look at the live dino and the obstacles, `obstacles` being their centers and sizes
+    pub fn new(
+        dino: &Dino,
+        dino_transform: &Transform,
+        dino_size: Vec2,
+        obstacles: impl Iterator<Item = (Vec2, Vec2)>,
+        status: &GameStatus,
+        config: &GameConfig,
+    ) -> Self {
+        let dino_left = dino_transform.translation.x - dino_size.x / 2.0;
+        let dino_right = dino_transform.translation.x + dino_size.x / 2.0;
+        let next = obstacles
+            .filter(|(center, size)| center.x + size.x / 2.0 > dino_left)
+            .min_by(|(a, _), (b, _)| a.x.total_cmp(&b.x));
+        let mut observation = Self {
+            dino_altitude: dino.altitude,
+            dino_velocity: dino.velocity,
+            dino_in_air: dino.in_air,
+            dino_ducking: dino.is_ducking,
+            world_speed: world_speed(status, config.tree_width),
+            ..default()
+        };
+        if let Some((center, size)) = next {
+            observation.obstacle_ahead = true;
+            observation.obstacle_distance = (center.x - size.x / 2.0 - dino_right).max(0.0);
+            observation.obstacle_width = size.x;
+            observation.obstacle_height = size.y;
+            observation.obstacle_altitude = center.y - size.y / 2.0 - config.ground_y_pos;
+        }
+        observation
+    }
+}
+
+/// Observation of the current tick, `None` without a live dino
+pub(crate) fn observe(
+    dinos: &Query<(&Dino, &Transform, &Sprite), Without<Ghost>>,
+    obstacles: &Query<(&Transform, &Sprite), With<Obstacle>>,
+    status: &GameStatus,
+    config: &GameConfig,
+) -> Option<Observation> {
+    let (dino, transform, sprite) = dinos.iter().next()?;
+    let obstacles = obstacles
+        .iter()
+        .map(|(transform, sprite)| (transform.translation.xy(), sprite_size(sprite, transform)));
+    Some(Observation::new(
+        dino,
+        transform,
+        sprite_size(sprite, transform),
+        obstacles,
+        status,
+        config,
+    ))
+}
+
+pub struct AgentPlugin;
+
+impl Plugin for AgentPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            apply_agent_input
+                .before(record_actions)
+                .in_set(SimulationSystems::Input)
+                .run_if(resource_exists::<AgentInput>),
+        );
+    }
+}
+
+/// Hold jump or duck as the agent asks, recorded like any other input
+pub(crate) fn apply_agent_input(input: Res<AgentInput>, mut actions: ResMut<FixedActionState>) {
+    for (action, held) in [
+        (GameAction::Jump, input.0 == AgentAction::Jump),
+        (GameAction::Duck, input.0 == AgentAction::Duck),
+    ] {
+        // Last tick's value is what the agent held, the live inputs only decided this one
+        let was_pressed = actions.was_pressed(action);
+        actions.set(action, held, was_pressed);
+    }
+}
@@ -0,0 +1,234 @@
+//! Line-delimited JSON protocol for agents driving the game as a subprocess.
+//!
+//! Before every simulation tick the game writes a [`AgentFrame`] line to stdout and waits for
+//! an [`AgentCommand`] line on stdin, for example `{"action":"jump"}`. Once the dino hits an
+//! obstacle a last frame with `"done":true` is written, answered with `{"reset":42}` (or
+//! `{"reset":null}` for a random seed) to start the next run, or `"quit"`. Closing stdin quits.
+
+use std::io::{BufRead, BufReader, Write};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent::{apply_agent_input, observe},
+    components::{Dino, Obstacle},
+    ghost::Ghost,
+    screens::start_playing,
+    AgentAction, AgentInput, GameConfig, GameScreen, GameStatus, InGame, Observation, RunSeed,
+    SimulationSystems,
+};
+
+/// What the game tells the agent, one line per tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentFrame {
+    /// Ticks played in the current run
+    pub tick: u64,
+    pub score: u64,
+    /// The run is over, the only answers left are a reset or quitting
+    pub done: bool,
+    pub observation: Observation,
+}
+
+/// What the agent answers, one line per frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentCommand {
+    /// Hold this action for the coming tick
+    Action(AgentAction),
+    /// Start a new run from this seed, or a random one
+    Reset(Option<u64>),
+    Quit,
+}
+
+/// Where the agent's commands come from and its frames go, stdin and stdout unless told otherwise
+#[derive(Resource)]
+pub struct AgentIo {
+    commands: Box<dyn BufRead + Send + Sync>,
+    frames: Box<dyn Write + Send + Sync>,
+}
+
+impl AgentIo {
+    pub fn new(
+        commands: impl BufRead + Send + Sync + 'static,
+        frames: impl Write + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            commands: Box::new(commands),
+            frames: Box::new(frames),
+        }
+    }
+
+    pub fn stdio() -> Self {
+        Self::new(BufReader::new(std::io::stdin()), std::io::stdout())
+    }
+
+    /// Write `frame` and wait for the answer, `None` once the commands run out
+    fn exchange(&mut self, frame: &AgentFrame) -> Option<AgentCommand> {
+        let line = serde_json::to_string(frame).expect("agent frames always serialize");
+        // Nobody listening any more, so nobody to answer either
+        writeln!(self.frames, "{}", line).ok()?;
+        self.frames.flush().ok()?;
+        loop {
+            let mut answer = String::new();
+            if self.commands.read_line(&mut answer).ok()? == 0 {
+                return None;
+            }
+            match serde_json::from_str(answer.trim()) {
+                Ok(command) => return Some(command),
+                Err(e) => warn!("Ignoring agent command {:?}: {}", answer.trim(), e),
+            }
+        }
+    }
+}
+
+/// Progress of the run the agent plays
+#[derive(Debug, Default, Resource)]
+struct AgentStdio {
+    tick: u64,
+    /// Observation at the end of the last tick, what the game over frame shows
+    last: Observation,
+}
+
+/// Drive the game from an agent over [`AgentIo`], see the module docs for the protocol.
+/// Meant for [`crate::app::AppType::Headless`], where nothing else writes to stdout.
+pub struct AgentStdioPlugin;
+
+impl Plugin for AgentStdioPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<AgentIo>() {
+            app.insert_resource(AgentIo::stdio());
+        }
+        app.init_resource::<AgentStdio>()
+            .init_resource::<AgentInput>()
+            .add_systems(OnEnter(InGame), reset_tick)
+            .add_systems(
+                FixedUpdate,
+                (
+                    exchange_tick
+                        .in_set(SimulationSystems::Input)
+                        .before(apply_agent_input),
+                    // The run is over by the time the game over frame is written,
+                    // and the dino and obstacles gone with it
+                    remember_observation
+                        .after(SimulationSystems::Score)
+                        .run_if(in_state(GameScreen::PlayScreen)),
+                ),
+            )
+            .add_systems(OnEnter(GameScreen::GameOverScreen), exchange_game_over)
+            .add_systems(
+                Update,
+                start_playing.run_if(in_state(GameScreen::StartScreen)),
+            );
+    }
+}
+
+fn reset_tick(mut stdio: ResMut<AgentStdio>) {
+    stdio.tick = 0;
+}
+
+type ObservedDinos<'w, 's> =
+    Query<'w, 's, (&'static Dino, &'static Transform, &'static Sprite), Without<Ghost>>;
+type ObservedObstacles<'w, 's> =
+    Query<'w, 's, (&'static Transform, &'static Sprite), With<Obstacle>>;
+
+fn observe_now(
+    dinos: &ObservedDinos,
+    obstacles: &ObservedObstacles,
+    status: &GameStatus,
+    config: &GameConfig,
+) -> Observation {
+    observe(dinos, obstacles, status, config).unwrap_or_default()
+}
+
+fn remember_observation(
+    mut stdio: ResMut<AgentStdio>,
+    dinos: ObservedDinos,
+    obstacles: ObservedObstacles,
+    status: Res<GameStatus>,
+    config: Res<GameConfig>,
+) {
+    stdio.last = observe_now(&dinos, &obstacles, &status, &config);
+}
+
+/// Start the next run from `seed`, by way of `screen`
+fn reset_run(
+    seed: Option<u64>,
+    screen: GameScreen,
+    run_seed: &mut RunSeed,
+    input: &mut AgentInput,
+    next_screen: &mut NextState<GameScreen>,
+) {
+    run_seed.0 = seed;
+    input.0 = AgentAction::Run;
+    next_screen.set(screen);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn exchange_tick(
+    mut io: ResMut<AgentIo>,
+    mut stdio: ResMut<AgentStdio>,
+    mut input: ResMut<AgentInput>,
+    mut run_seed: ResMut<RunSeed>,
+    mut next_screen: ResMut<NextState<GameScreen>>,
+    mut exit: MessageWriter<AppExit>,
+    dinos: ObservedDinos,
+    obstacles: ObservedObstacles,
+    status: Res<GameStatus>,
+    config: Res<GameConfig>,
+) {
+    let frame = AgentFrame {
+        tick: stdio.tick,
+        score: status.score,
+        done: false,
+        observation: observe_now(&dinos, &obstacles, &status, &config),
+    };
+    stdio.tick += 1;
+    match io.exchange(&frame) {
+        Some(AgentCommand::Action(action)) => input.0 = action,
+        // Leave the run, the next one starts from the start screen
+        Some(AgentCommand::Reset(seed)) => reset_run(
+            seed,
+            GameScreen::StartScreen,
+            &mut run_seed,
+            &mut input,
+            &mut next_screen,
+        ),
+        Some(AgentCommand::Quit) | None => {
+            exit.write(AppExit::Success);
+        }
+    }
+}
+
+fn exchange_game_over(
+    mut io: ResMut<AgentIo>,
+    stdio: Res<AgentStdio>,
+    mut input: ResMut<AgentInput>,
+    mut run_seed: ResMut<RunSeed>,
+    mut next_screen: ResMut<NextState<GameScreen>>,
+    mut exit: MessageWriter<AppExit>,
+    status: Res<GameStatus>,
+) {
+    let frame = AgentFrame {
+        tick: stdio.tick,
+        score: status.score,
+        done: true,
+        observation: stdio.last,
+    };
+    let seed = match io.exchange(&frame) {
+        Some(AgentCommand::Reset(seed)) => seed,
+        // Acting on a finished run makes no sense, take it as a reset with a random seed
+        Some(AgentCommand::Action(_)) => None,
+        Some(AgentCommand::Quit) | None => {
+            exit.write(AppExit::Success);
+            return;
+        }
+    };
+    reset_run(
+        seed,
+        GameScreen::PlayScreen,
+        &mut run_seed,
+        &mut input,
+        &mut next_screen,
+    );
+}
@@ -1,9 +1,24 @@
+use std::path::PathBuf;
+
 use crate::{
-    game_logic::GameLogicPlugin, setup_2d_camera, update_window_size, DebugPlugin, DinoPlugin,
-    GameConfig, GameControlPlugin, GameOverPlugin, GameScreen, GameStartPlugin, GameStatus,
-    GroundPlugin, SpeedControlInfo, TreePlugin,
+    debug::DebugWindowState, game_logic::GameLogicPlugin, setup_2d_camera, update_window_size,
+    utils::EguiWantsPointer, AgentInput, AgentIo, AgentPlugin, AgentStdioPlugin, AudioMuted,
+    Autopilot, AutopilotPlugin, ConfigPath, DebugPlugin, DinoPlugin, GameAudioPlugin, GameConfig,
+    GameControlPlugin, GameInputPlugin, GameOverPlugin, GameRngPlugin, GameScreen, GameStartPlugin,
+    GameStatus, GhostPlugin, GroundPlugin, HighScorePlugin, HudPlugin, InGame, InputBindings,
+    InterpolationPlugin, LastReplayPlugin, NeuralPolicy, ObstaclePlugin, PolicyPlugin,
+    RenderToImageConfig, RenderToImagePlugin, Replay, ReplayPlayback, ReplayPlugin, RunSeed,
+    ScorePlugin, SimulationPlugin, SpeedControlInfo, INITIAL_GAME_SPEED,
+};
+use bevy::{
+    app::{PluginGroupBuilder, PluginsState},
+    input::InputPlugin,
+    prelude::*,
+    state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+    window::{ExitCondition, MonitorSelection, PrimaryWindow, WindowMode},
+    winit::WinitPlugin,
 };
-use bevy::{app::PluginGroupBuilder, prelude::*, winit::WinitPlugin};
 use bevy_kira_audio::prelude::AudioPlugin as KiraAudioPlugin;
 
 pub struct Game {
@@ -15,6 +30,9 @@ pub struct Game {
 pub enum AppType {
     Normal,
     RenderToImageTesting,
+    /// Gameplay only: no window, renderer, egui or audio.
+    /// Every update advances the game by exactly one fixed timestep.
+    Headless,
 }
 
 fn default_plugins(app_type: AppType) -> PluginGroupBuilder {
@@ -25,11 +43,17 @@ fn default_plugins(app_type: AppType) -> PluginGroupBuilder {
             fit_canvas_to_parent: true,
             ..Default::default()
         }),
-        AppType::RenderToImageTesting => None,
+        AppType::RenderToImageTesting | AppType::Headless => None,
+    };
+    // Without a window the app would exit right away, the capture decides when to stop instead
+    let exit_condition = match app_type {
+        AppType::Normal => ExitCondition::OnAllClosed,
+        AppType::RenderToImageTesting | AppType::Headless => ExitCondition::DontExit,
     };
     let plugin = DefaultPlugins
         .set(WindowPlugin {
             primary_window,
+            exit_condition,
             ..Default::default()
         })
         .set(AssetPlugin {
@@ -39,58 +63,221 @@ fn default_plugins(app_type: AppType) -> PluginGroupBuilder {
         });
 
     match app_type {
-        AppType::RenderToImageTesting => plugin
+        AppType::RenderToImageTesting | AppType::Headless => plugin
             .disable::<WinitPlugin>()
             .set(ImagePlugin::default_nearest()),
         AppType::Normal => plugin,
     }
 }
 
+/// Just enough for the gameplay plugins: time, states and input
+fn headless_plugins() -> PluginGroupBuilder {
+    MinimalPlugins.build().add(StatesPlugin).add(InputPlugin)
+}
+
 impl Game {
     pub fn init(app_type: AppType) -> Self {
         let mut game = Game { app: App::new() };
         // Load game config from file or use defaults
-        let game_config = GameConfig::load_from_file();
+        let config_path = ConfigPath::default();
+        let game_config = GameConfig::load_from_file(&config_path.0);
+        match app_type {
+            AppType::Normal | AppType::RenderToImageTesting => {
+                game.app.add_plugins(default_plugins(app_type));
+            }
+            AppType::Headless => {
+                game.app
+                    .add_plugins(headless_plugins())
+                    .insert_resource(TimeUpdateStrategy::FixedTimesteps(1));
+            }
+        };
         game.app
-            .add_plugins(default_plugins(app_type))
             .insert_resource(GameStatus {
-                speed: 5,
+                speed: INITIAL_GAME_SPEED,
                 score: 0,
                 window_width: 1920.0,
                 window_height: 1080.0,
             })
             .insert_resource(game_config)
+            .insert_resource(config_path)
             .init_state::<GameScreen>()
+            .add_computed_state::<InGame>()
             .insert_resource(ClearColor(Color::srgb(1.0, 1.0, 1.0)))
-            .insert_resource(SpeedControlInfo {
-                speed_increment: 100,
-                max_game_speed: u64::MAX,
-            })
+            .insert_resource(SpeedControlInfo::INITIAL)
+            .init_resource::<EguiWantsPointer>()
             .add_plugins((
+                SimulationPlugin,
+                GameInputPlugin,
+                GameRngPlugin,
                 DinoPlugin,
-                GameControlPlugin,
                 GameLogicPlugin,
-                TreePlugin,
+                ObstaclePlugin,
                 GroundPlugin,
+                ScorePlugin,
+                ReplayPlugin,
+                GhostPlugin,
+                AutopilotPlugin,
+                AgentPlugin,
+                PolicyPlugin,
+            ));
+        if !matches!(app_type, AppType::Headless) {
+            game.app.add_plugins((
+                InterpolationPlugin,
+                GameControlPlugin,
                 GameStartPlugin,
                 DebugPlugin,
                 GameOverPlugin,
+                HudPlugin,
                 KiraAudioPlugin,
+                GameAudioPlugin,
             ));
+        }
         match app_type {
             AppType::Normal => {
+                // Only the real game reads and writes the player's saved scores and replays,
+                // so test runs neither show nor overwrite them
                 game.app
+                    .add_plugins((HighScorePlugin, LastReplayPlugin))
                     .add_systems(Startup, setup_2d_camera)
-                    .add_systems(Update, update_window_size);
+                    // A run keeps the width it started with, the simulation depends on it
+                    .add_systems(Update, update_window_size.run_if(not(in_state(InGame))));
             }
             AppType::RenderToImageTesting => {
-                todo!("Follow bevy render test example to setup one render to image test");
+                game.app.add_plugins(RenderToImagePlugin);
             }
+            AppType::Headless => {}
         };
         game
     }
 
+    /// Override the [`RenderToImageConfig`] used by [`AppType::RenderToImageTesting`]
+    pub fn with_render_to_image_config(mut self, config: RenderToImageConfig) -> Self {
+        self.app.insert_resource(config);
+        self
+    }
+
+    /// Load the config from `path` instead of [`GameConfig::CONFIG_FILE`],
+    /// and the input bindings from the same directory. Exports from the debug window go there too.
+    pub fn with_config_file(mut self, path: impl Into<PathBuf>) -> Self {
+        let config_path = ConfigPath(path.into());
+        self.app
+            .insert_resource(GameConfig::load_from_file(&config_path.0))
+            .insert_resource(InputBindings::load_from_file(config_path.bindings_file()))
+            .insert_resource(config_path);
+        self
+    }
+
+    /// Change the primary window, does nothing for apps without one
+    fn update_primary_window(&mut self, update: impl FnOnce(&mut Window)) {
+        let world = self.app.world_mut();
+        let mut windows = world.query_filtered::<&mut Window, With<PrimaryWindow>>();
+        if let Ok(mut window) = windows.single_mut(world) {
+            update(&mut window);
+        }
+    }
+
+    /// Open the window at `width` x `height` logical pixels
+    pub fn with_window_size(mut self, width: f32, height: f32) -> Self {
+        self.update_primary_window(|window| window.resolution.set(width, height));
+        self
+    }
+
+    /// Open the window borderless fullscreen on the current monitor
+    pub fn with_fullscreen(mut self) -> Self {
+        self.update_primary_window(|window| {
+            window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+        });
+        self
+    }
+
+    /// Move on from the start screen to `screen` on the first frame
+    pub fn with_start_screen(mut self, screen: GameScreen) -> Self {
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<GameScreen>>()
+            .set(screen);
+        self
+    }
+
+    /// Keep the game silent
+    pub fn with_muted_audio(mut self) -> Self {
+        self.app.insert_resource(AudioMuted(true));
+        self
+    }
+
+    /// Open with the debug window showing, does nothing for apps without one
+    pub fn with_debug_window(mut self) -> Self {
+        if let Some(mut state) = self.app.world_mut().get_resource_mut::<DebugWindowState>() {
+            state.visible = true;
+        }
+        self
+    }
+
+    /// Start every run from `seed`, overriding [`GameConfig::seed`]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.app.insert_resource(RunSeed(Some(seed)));
+        self
+    }
+
+    /// Play every run from `replay` instead of the live jump and duck inputs,
+    /// with the seed and config it was recorded with
+    pub fn with_replay(mut self, replay: Replay) -> Self {
+        self.app
+            .insert_resource(RunSeed(Some(replay.seed)))
+            .insert_resource(replay.config.clone())
+            .insert_resource(ReplayPlayback::new(replay));
+        self
+    }
+
+    /// Let the autopilot play, starting a new run whenever one ends
+    pub fn with_autopilot(mut self) -> Self {
+        self.app.insert_resource(Autopilot::new(true));
+        self
+    }
+
+    /// Let `policy` play the jump and duck actions
+    pub fn with_policy(mut self, policy: NeuralPolicy) -> Self {
+        self.app
+            .insert_resource(AgentInput::default())
+            .insert_resource(policy);
+        self
+    }
+
+    /// Let an agent play over stdin and stdout, see [`AgentStdioPlugin`].
+    /// Only makes sense for [`AppType::Headless`].
+    pub fn with_agent_stdio(self) -> Self {
+        self.with_agent_io(AgentIo::stdio())
+    }
+
+    /// Let an agent play the stdin and stdout protocol over `io` instead
+    pub fn with_agent_io(mut self, io: AgentIo) -> Self {
+        self.app.insert_resource(io).add_plugins(AgentStdioPlugin);
+        self
+    }
+
     pub fn run(mut self) -> AppExit {
         self.app.run()
     }
+
+    /// Run a single frame, for driving the game by hand instead of through [`Game::run`]
+    pub fn update(&mut self) {
+        if self.app.plugins_state() == PluginsState::Ready {
+            self.app.finish();
+            self.app.cleanup();
+        }
+        self.app.update();
+    }
+
+    /// Whether the game asked to exit, for driving it by hand instead of through [`Game::run`]
+    pub fn should_exit(&self) -> Option<AppExit> {
+        self.app.should_exit()
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
 }
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use bevy_kira_audio::{Audio, AudioControl, AudioInstance};
+
+use crate::{DinoJumpMusic, DinoJumped, DinoLanded, InGame};
+
+/// Play the game sounds, kept apart from gameplay so simulations can run without an audio device
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<JumpSoundInstance>()
+            .init_resource::<AudioMuted>()
+            .add_systems(OnEnter(InGame), load_dino_jump_music)
+            .add_systems(
+                Update,
+                (
+                    play_jump_sound.run_if(not_muted),
+                    stop_jump_sound_on_landing,
+                )
+                    .chain()
+                    .run_if(resource_exists::<DinoJumpMusic>),
+            )
+            .add_systems(OnExit(InGame), clean_dino_jump_music);
+    }
+}
+
+/// Keep the game silent, sounds already playing run out on their own
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AudioMuted(pub bool);
+
+fn not_muted(muted: Res<AudioMuted>) -> bool {
+    !muted.0
+}
+
+/// The jump sound currently playing, cut off once the dino lands
+#[derive(Resource, Default)]
+struct JumpSoundInstance(Option<Handle<AudioInstance>>);
+
+fn load_dino_jump_music(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let sound = asset_server.load("Jump.ogg");
+    commands.insert_resource(DinoJumpMusic(sound));
+}
+
+fn clean_dino_jump_music(mut commands: Commands) {
+    commands.remove_resource::<DinoJumpMusic>();
+}
+
+fn play_jump_sound(
+    mut jumped: MessageReader<DinoJumped>,
+    sound: Res<DinoJumpMusic>,
+    audio: Res<Audio>,
+    mut instance: ResMut<JumpSoundInstance>,
+) {
+    for _ in jumped.read() {
+        instance.0 = Some(audio.play(sound.clone()).handle());
+    }
+}
+
+fn stop_jump_sound_on_landing(
+    mut landed: MessageReader<DinoLanded>,
+    mut instance: ResMut<JumpSoundInstance>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    for _ in landed.read() {
+        if let Some(handle) = instance.0.take() {
+            if let Some(instance) = audio_instances.get_mut(&handle) {
+                instance.pause(Default::default());
+            }
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! A rule-based player: it looks at the obstacles coming up and jumps or ducks in time.
+//! With it on the game keeps starting new runs by itself, for demos and soak tests.
+
+use bevy::prelude::*;
+
+use crate::{
+    components::{Dino, Obstacle},
+    game_logic::sprite_size,
+    ghost::Ghost,
+    obstacle::world_speed,
+    replay::record_actions,
+    screens::start_playing,
+    FixedActionState, GameAction, GameConfig, GameScreen, GameStatus, ReplayPlayback,
+    SimulationSystems,
+};
+
+/// How long the game over screen stays up before the next run starts
+const ATTRACT_RESTART_SECS: f32 = 2.0;
+/// How long before a bird reaches the dino it ducks under it, in seconds
+const DUCK_LEAD_SECS: f32 = 0.1;
+/// Extra height kept above an obstacle when planning a jump, in pixels
+const JUMP_MARGIN: f32 = 2.0;
+
+/// Lets the game play itself
+#[derive(Debug, Resource)]
+pub struct Autopilot {
+    /// Play the jump and duck actions, and start a new run from the start and game over screens
+    pub enabled: bool,
+    /// Time left on the game over screen before the next run
+    restart: Timer,
+}
+
+impl Autopilot {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            restart: Timer::from_seconds(ATTRACT_RESTART_SECS, TimerMode::Once),
+        }
+    }
+}
+
+impl Default for Autopilot {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+pub struct AutopilotPlugin;
+
+impl Plugin for AutopilotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Autopilot>()
+            .add_systems(
+                FixedUpdate,
+                drive_actions
+                    .before(record_actions)
+                    .in_set(SimulationSystems::Input)
+                    .run_if(autopilot_enabled.and(not(resource_exists::<ReplayPlayback>))),
+            )
+            .add_systems(OnEnter(GameScreen::GameOverScreen), reset_restart_timer)
+            .add_systems(
+                Update,
+                (
+                    start_playing.run_if(in_state(GameScreen::StartScreen)),
+                    restart_after_game_over.run_if(in_state(GameScreen::GameOverScreen)),
+                )
+                    .run_if(autopilot_enabled),
+            );
+    }
+}
+
+fn autopilot_enabled(autopilot: Res<Autopilot>) -> bool {
+    autopilot.enabled
+}
+
+fn reset_restart_timer(mut autopilot: ResMut<Autopilot>) {
+    autopilot.restart.reset();
+}
+
+fn restart_after_game_over(
+    time: Res<Time>,
+    mut autopilot: ResMut<Autopilot>,
+    mut next_screen: ResMut<NextState<GameScreen>>,
+) {
+    if autopilot.restart.tick(time.delta()).just_finished() {
+        next_screen.set(GameScreen::PlayScreen);
+    }
+}
+
+/// Obstacles close enough together to be cleared in one go, as one box
+#[derive(Debug, Clone, Copy)]
+struct ObstacleGroup {
+    left: f32,
+    right: f32,
+    /// Height of the top above the ground
+    top: f32,
+    /// Height of the underside above the ground
+    bottom: f32,
+}
+
+/// What the dino has to do about an obstacle group
+enum Plan {
+    /// It flies over a running dino
+    Ignore,
+    /// Duck once it is this many seconds away
+    Duck { lead_secs: f32 },
+    /// Jump once it is this many seconds away, holding it for `hold_secs`
+    Jump { lead_secs: f32, hold_secs: f32 },
+}
+
+impl ObstacleGroup {
+    /// `tap_secs` is the shortest a jump can be held, a single tick
+    fn plan(&self, config: &GameConfig, speed: f32, tap_secs: f32) -> Plan {
+        if self.bottom >= config.dino_height {
+            return Plan::Ignore;
+        }
+        if self.bottom >= config.dino_duck_height {
+            return Plan::Duck {
+                lead_secs: DUCK_LEAD_SECS,
+            };
+        }
+        // Be above the group from its front reaching the dino until its back is past it,
+        // with whatever time is left over split evenly on both sides.
+        // A tap lands sooner, so it is only held when a tap doesn't clear the group.
+        let height = self.top + JUMP_MARGIN;
+        let needed = (self.right - self.left + config.dino_width) / speed;
+        let jump = |hold_secs: f32| {
+            let (enter, exit) = jump_window(config, hold_secs, height)?;
+            Some((enter, exit - enter - needed, hold_secs))
+        };
+        let held = jump(config.dino_max_jump_hold);
+        let (enter, slack, hold_secs) = jump(tap_secs)
+            .filter(|(_, slack, _)| *slack >= 0.0)
+            .or(held)
+            // Too high to clear, the best that can be done is going as high as possible
+            .unwrap_or((0.0, 0.0, config.dino_max_jump_hold));
+        Plan::Jump {
+            lead_secs: enter + slack.max(0.0) / 2.0,
+            hold_secs,
+        }
+    }
+}
+
+/// When a jump held for `hold_secs` rises above `height` and comes back down below it,
+/// in seconds after the jump, `None` if it never gets that high
+fn jump_window(config: &GameConfig, hold_secs: f32, height: f32) -> Option<(f32, f32)> {
+    let velocity = config.dino_jump_velocity;
+    let gravity = config.dino_gravity;
+    let hold_height = velocity * hold_secs;
+    let discriminant = velocity * velocity + 2.0 * gravity * (hold_height - height);
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let enter = if height <= hold_height {
+        height / velocity
+    } else {
+        hold_secs + (velocity - root) / gravity
+    };
+    let exit = hold_secs + (velocity + root) / gravity;
+    Some((enter, exit))
+}
+
+/// Groups of obstacles not yet past `dino_left`, nearest first
+fn obstacle_groups(
+    obstacles: impl Iterator<Item = (Vec2, Vec2)>,
+    dino_left: f32,
+    config: &GameConfig,
+) -> Vec<ObstacleGroup> {
+    let mut boxes: Vec<ObstacleGroup> = obstacles
+        .map(|(center, size)| ObstacleGroup {
+            left: center.x - size.x / 2.0,
+            right: center.x + size.x / 2.0,
+            top: center.y + size.y / 2.0 - config.ground_y_pos,
+            bottom: center.y - size.y / 2.0 - config.ground_y_pos,
+        })
+        .filter(|group| group.right > dino_left)
+        .collect();
+    boxes.sort_by(|a, b| a.left.total_cmp(&b.left));
+
+    let mut groups: Vec<ObstacleGroup> = Vec::new();
+    for next in boxes {
+        match groups.last_mut() {
+            // Too close to land in between
+            Some(group) if next.left - group.right < config.dino_width => {
+                group.right = group.right.max(next.right);
+                group.top = group.top.max(next.top);
+                group.bottom = group.bottom.min(next.bottom);
+            }
+            _ => groups.push(next),
+        }
+    }
+    groups
+}
+
+/// Press jump and duck for the live dino, like a player watching the obstacles would
+fn drive_actions(
+    dinos: Query<(&Dino, &Transform, &Sprite), Without<Ghost>>,
+    obstacles: Query<(&Transform, &Sprite), With<Obstacle>>,
+    time: Res<Time>,
+    status: Res<GameStatus>,
+    config: Res<GameConfig>,
+    mut actions: ResMut<FixedActionState>,
+) {
+    let Some((dino, dino_transform, dino_sprite)) = dinos.iter().next() else {
+        return;
+    };
+    let speed = world_speed(&status, config.tree_width);
+    let dino_half_width = sprite_size(dino_sprite, dino_transform).x / 2.0;
+    let dino_left = dino_transform.translation.x - dino_half_width;
+    let dino_right = dino_transform.translation.x + dino_half_width;
+    let groups = obstacle_groups(
+        obstacles.iter().map(|(transform, sprite)| {
+            (transform.translation.xy(), sprite_size(sprite, transform))
+        }),
+        dino_left,
+        &config,
+    );
+
+    let mut duck = false;
+    let mut launch = false;
+    let mut hold = false;
+    for group in groups {
+        let secs_away = (group.left - dino_right).max(0.0) / speed;
+        match group.plan(&config, speed, time.delta_secs()) {
+            Plan::Ignore => continue,
+            Plan::Duck { lead_secs } => duck = secs_away <= lead_secs,
+            Plan::Jump {
+                lead_secs,
+                hold_secs,
+            } => {
+                launch = secs_away <= lead_secs;
+                hold = dino.airborne_secs < hold_secs;
+            }
+        }
+        break;
+    }
+
+    // What the autopilot held last tick, the live inputs only decided the current one
+    let was_jumping = actions.was_pressed(GameAction::Jump);
+    // Let go on landing, so the next jump is a fresh press
+    let jump = if dino.in_air {
+        was_jumping && hold
+    } else {
+        launch && !was_jumping
+    };
+    actions.set(GameAction::Jump, jump && !duck, was_jumping);
+    let was_ducking = actions.was_pressed(GameAction::Duck);
+    actions.set(GameAction::Duck, duck, was_ducking);
+}
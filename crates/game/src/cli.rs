@@ -0,0 +1,116 @@
+//! Command-line flags of the game binary.
+
+use std::path::PathBuf;
+
+use crate::GameScreen;
+
+/// What `--help` prints
+pub const HELP: &str = "\
+Dinosaur Game
+
+Usage: dinosaur-game [OPTIONS]
+
+Options:
+  --config <FILE>           Load and export the config here instead of game_config.json,
+                            input bindings are kept in the same directory
+  --seed <NUMBER>           Start every run from this seed
+  --window-size <WxH>       Window size in logical pixels, for example 1280x720
+  --fullscreen              Open borderless fullscreen
+  --screen <start|play>     Screen to open on [default: start]
+  --mute                    Play without sound
+  --debug                   Open with the debug window showing
+  --replay <FILE>           Play back a recorded replay, with its seed and config
+  --policy <FILE>           Let a trained network play, see dino-trainer
+  --autopilot               Let the autopilot play
+  --agent-stdio             Let an agent play over stdin and stdout, without a window
+  -h, --help                Print this help
+";
+
+/// Everything the game binary can be told on its command line
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliArgs {
+    pub help: bool,
+    pub config: Option<PathBuf>,
+    pub seed: Option<u64>,
+    /// Width and height in logical pixels
+    pub window_size: Option<(f32, f32)>,
+    pub fullscreen: bool,
+    pub screen: Option<GameScreen>,
+    pub mute: bool,
+    pub debug: bool,
+    pub replay: Option<PathBuf>,
+    pub policy: Option<PathBuf>,
+    pub autopilot: bool,
+    pub agent_stdio: bool,
+}
+
+/// Parse a `<width>x<height>` window size
+fn parse_window_size(value: &str) -> Option<(f32, f32)> {
+    let (width, height) = value.split_once(['x', 'X'])?;
+    let width: f32 = width.trim().parse().ok()?;
+    let height: f32 = height.trim().parse().ok()?;
+    (width > 0.0 && height > 0.0).then_some((width, height))
+}
+
+fn parse_screen(value: &str) -> Option<GameScreen> {
+    match value {
+        "start" => Some(GameScreen::StartScreen),
+        "play" => Some(GameScreen::PlayScreen),
+        _ => None,
+    }
+}
+
+impl CliArgs {
+    /// Parse the arguments after the program name, flags take their value as
+    /// `--<name> <value>` or `--<name>=<value>`
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => {
+                    (name.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{} needs a value", name))
+            };
+            // Bare flags are on by being there, `--mute=no` is a mistake rather than a value
+            let on = || match inline_value {
+                Some(_) => Err(format!("{} takes no value", name)),
+                None => Ok(true),
+            };
+            let invalid = |value: &str| format!("Invalid {} value {:?}", name, value);
+            match name.as_str() {
+                "-h" | "--help" => cli.help = on()?,
+                "--config" => cli.config = Some(value()?.into()),
+                "--seed" => {
+                    let value = value()?;
+                    cli.seed = Some(value.parse().map_err(|_| invalid(&value))?);
+                }
+                "--window-size" => {
+                    let value = value()?;
+                    cli.window_size =
+                        Some(parse_window_size(&value).ok_or_else(|| invalid(&value))?);
+                }
+                "--fullscreen" => cli.fullscreen = on()?,
+                "--screen" => {
+                    let value = value()?;
+                    cli.screen = Some(parse_screen(&value).ok_or_else(|| invalid(&value))?);
+                }
+                "--mute" => cli.mute = on()?,
+                "--debug" => cli.debug = on()?,
+                "--replay" => cli.replay = Some(value()?.into()),
+                "--policy" => cli.policy = Some(value()?.into()),
+                "--autopilot" => cli.autopilot = on()?,
+                "--agent-stdio" => cli.agent_stdio = on()?,
+                _ => return Err(format!("Unknown argument {:?}", name)),
+            }
+        }
+        Ok(cli)
+    }
+}
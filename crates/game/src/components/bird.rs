@@ -0,0 +1,92 @@
+use bevy::{
+    color::Color,
+    math::{Vec2, Vec3},
+    prelude::Component,
+    sprite::Sprite,
+    time::{Timer, TimerMode},
+    transform::components::Transform,
+    utils::default,
+};
+
+use crate::{components::Obstacle, GameConfig};
+
+/// Height of the bird's underside above the ground for each flight level, in dino heights.
+/// Low birds have to be jumped over, middle ones ducked under or jumped over,
+/// high ones fly over a running dino.
+pub const BIRD_FLIGHT_LEVELS: [f32; 3] = [0.1, 0.65, 1.1];
+
+/// Time each wing position is shown for
+const FLAP_INTERVAL_SECS: f32 = 0.2;
+
+const BIRD_COLOR: Color = Color::srgb(0.35, 0.35, 0.35);
+
+#[derive(Component)]
+#[require(Obstacle)]
+pub struct Bird {
+    /// Index into [`BIRD_FLIGHT_LEVELS`]
+    pub flight_level: usize,
+}
+
+impl Bird {
+    /// Create a new Bird flying at `flight_level`, centered on `x`
+    pub fn new(config: &GameConfig, flight_level: usize, x: f32) -> (Self, Sprite, Transform) {
+        let bird = Self { flight_level };
+        let pos = Vec3::new(x, bird.y_pos(config), 0.0);
+        (
+            bird,
+            Sprite {
+                color: BIRD_COLOR,
+                custom_size: Some(Vec2::new(config.bird_width, config.bird_height)),
+                ..default()
+            },
+            Transform::from_translation(pos),
+        )
+    }
+
+    /// Y position of the bird's center
+    pub fn y_pos(&self, config: &GameConfig) -> f32 {
+        config.ground_y_pos
+            + BIRD_FLIGHT_LEVELS[self.flight_level] * config.dino_height
+            + config.bird_height / 2.0
+    }
+}
+
+/// The wing drawn on a [`Bird`], flapping between up and down.
+/// Only the bird's body counts for collisions.
+#[derive(Component)]
+pub struct BirdWing {
+    pub flap: Timer,
+    pub up: bool,
+}
+
+impl BirdWing {
+    pub fn new(config: &GameConfig) -> (Self, Sprite, Transform) {
+        let wing = Self {
+            flap: Timer::from_seconds(FLAP_INTERVAL_SECS, TimerMode::Repeating),
+            up: true,
+        };
+        let pos = Vec3::new(0.0, wing.y_offset(config), 0.0);
+        (
+            wing,
+            Sprite {
+                color: BIRD_COLOR,
+                custom_size: Some(Self::size(config)),
+                ..default()
+            },
+            Transform::from_translation(pos),
+        )
+    }
+
+    pub fn size(config: &GameConfig) -> Vec2 {
+        Vec2::new(config.bird_width / 2.0, config.bird_height)
+    }
+
+    /// Offset from the bird's center for the current wing position
+    pub fn y_offset(&self, config: &GameConfig) -> f32 {
+        if self.up {
+            config.bird_height
+        } else {
+            -config.bird_height
+        }
+    }
+}
@@ -1,31 +1,61 @@
-use bevy::prelude::Handle;
 use bevy::{
     color::Color,
     math::{Vec2, Vec3},
     prelude::Component,
     sprite::Sprite,
-    time::{Time, Virtual},
     transform::components::Transform,
     utils::default,
 };
-use bevy_kira_audio::AudioInstance;
 
-use crate::GameConfig;
+use crate::{
+    components::{Hitboxes, PreviousPosition},
+    GameConfig,
+};
 
 /// Default dark color for dino
 pub const DINO_DEFAULT_COLOR: Color = Color::srgb(0.05, 0.05, 0.05);
-/// Red color for dino when touched
-pub const DINO_TOUCHED_COLOR: Color = Color::srgb(0.8, 0.1, 0.1);
+
+/// See-through color for the ghost of an earlier run
+pub const DINO_GHOST_COLOR: Color = Color::srgba(0.05, 0.05, 0.05, 0.3);
 
 #[derive(Component, Default)]
+#[require(Hitboxes, PreviousPosition)]
 pub struct Dino {
-    pub in_air_start_time: Option<Time<Virtual>>,
-    pub jump_sound: Option<Handle<AudioInstance>>,
-    /// Whether the dino has been touched (turns red)
-    pub is_touched: bool,
+    /// Feet off the ground, whether from a jump or not
+    pub in_air: bool,
+    /// In the air because of a jump, which rules out a coyote-time jump
+    pub is_jumping: bool,
+    /// Time since the feet left the ground, in seconds
+    pub airborne_secs: f32,
+    /// Age of a jump press still waiting for the dino to be able to jump, in seconds
+    pub buffered_jump_secs: Option<f32>,
+    /// Height of the dino's feet above the ground, in pixels
+    pub altitude: f32,
+    /// Vertical speed in pixels per second, positive is up
+    pub velocity: f32,
+    /// How long the current jump has been held, `None` once released or held to the limit
+    pub jump_hold_secs: Option<f32>,
+    /// Crouching on the ground, shorter so it fits under middle birds
+    pub is_ducking: bool,
 }
 
 impl Dino {
+    /// Whether a jump can start now: on the ground, or just off it without having jumped
+    pub fn can_jump(&self, config: &GameConfig) -> bool {
+        let grounded =
+            !self.in_air || (!self.is_jumping && self.airborne_secs <= config.coyote_time_secs);
+        grounded && !self.is_ducking
+    }
+
+    /// Sprite and collision height for the current pose
+    pub fn height(&self, config: &GameConfig) -> f32 {
+        if self.is_ducking {
+            config.dino_duck_height
+        } else {
+            config.dino_height
+        }
+    }
+
     /// Create a new Dino with the given config
     pub fn new(config: &GameConfig) -> (Self, Sprite, Transform) {
         Self::new_with_color(config, DINO_DEFAULT_COLOR)
@@ -36,9 +66,14 @@ impl Dino {
         let size = Vec2::new(config.dino_width, config.dino_height);
         (
             Self {
-                in_air_start_time: None,
-                jump_sound: None,
-                is_touched: false,
+                in_air: false,
+                is_jumping: false,
+                airborne_secs: 0.0,
+                buffered_jump_secs: None,
+                altitude: 0.0,
+                velocity: 0.0,
+                jump_hold_secs: None,
+                is_ducking: false,
             },
             Sprite {
                 color,
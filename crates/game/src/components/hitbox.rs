@@ -0,0 +1,44 @@
+use bevy::{
+    math::{bounding::Aabb2d, Vec2},
+    prelude::Component,
+};
+use serde::{Deserialize, Serialize};
+
+/// A collision rectangle, in fractions of the sprite size measured from its center.
+/// `center: (0, 0), size: (1, 1)` covers the whole sprite.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hitbox {
+    pub center: Vec2,
+    pub size: Vec2,
+}
+
+impl Hitbox {
+    pub const fn new(center: Vec2, size: Vec2) -> Self {
+        Self { center, size }
+    }
+
+    /// Where the box is for a sprite of `sprite_size` centered on `position`
+    pub fn aabb(&self, position: Vec2, sprite_size: Vec2) -> Aabb2d {
+        Aabb2d::new(
+            position + self.center * sprite_size,
+            self.size * sprite_size / 2.0,
+        )
+    }
+}
+
+/// What an entity collides with, kept in sync with [`crate::GameConfig`]
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct Hitboxes(pub Vec<Hitbox>);
+
+impl Hitboxes {
+    /// Every box for a sprite of `sprite_size` centered on `position`
+    pub fn aabbs(&self, position: Vec2, sprite_size: Vec2) -> impl Iterator<Item = Aabb2d> + '_ {
+        self.0
+            .iter()
+            .map(move |hitbox| hitbox.aabb(position, sprite_size))
+    }
+}
+
+/// Position at the start of the current simulation tick, `None` until one started
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct PreviousPosition(pub Option<Vec2>);
@@ -1,6 +1,12 @@
+mod bird;
 mod dino;
 mod ground;
+mod hitbox;
+mod obstacle;
 mod tree;
-pub use dino::{Dino, DINO_DEFAULT_COLOR, DINO_TOUCHED_COLOR};
+pub use bird::{Bird, BirdWing, BIRD_FLIGHT_LEVELS};
+pub use dino::{Dino, DINO_DEFAULT_COLOR, DINO_GHOST_COLOR};
 pub use ground::Ground;
+pub use hitbox::{Hitbox, Hitboxes, PreviousPosition};
+pub use obstacle::Obstacle;
 pub use tree::Tree;
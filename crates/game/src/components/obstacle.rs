@@ -0,0 +1,12 @@
+use bevy::prelude::Component;
+
+use crate::{
+    components::{Hitboxes, PreviousPosition},
+    interpolation::TickTranslation,
+};
+
+/// Anything the dino has to get past.
+/// Obstacles move towards the dino and are despawned once they leave the screen.
+#[derive(Component, Default)]
+#[require(Hitboxes, PreviousPosition, TickTranslation)]
+pub struct Obstacle;
@@ -7,9 +7,10 @@ use bevy::{
     utils::default,
 };
 
-use crate::GameConfig;
+use crate::{components::Obstacle, GameConfig};
 
 #[derive(Component, Default)]
+#[require(Obstacle)]
 pub struct Tree;
 
 impl Tree {
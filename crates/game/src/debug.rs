@@ -1,14 +1,23 @@
 use bevy::{
-    app::{Plugin, Update},
+    app::{Plugin, PostUpdate, PreUpdate, Update},
     diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
-    input::ButtonInput,
-    prelude::{KeyCode, Res, ResMut, Resource},
+    ecs::schedule::IntoScheduleConfigs,
+    input::{gamepad::Gamepad, ButtonInput},
+    prelude::{KeyCode, MouseButton, Query, Res, ResMut, Resource, World},
     state::state::{NextState, State},
     time::{Time, Virtual},
+    transform::TransformSystems,
 };
 use bevy_egui::{egui, EguiContexts, EguiPlugin, EguiPrimaryContextPass};
 
-use crate::{GameConfig, GameScreen, GameStatus};
+use crate::{
+    debug_gizmos::{draw_ground_and_thresholds, draw_hitboxes, draw_jump_arcs, gizmos_enabled},
+    input::resolve_actions,
+    step_one_tick,
+    utils::{egui_wants_pointer, EguiWantsPointer},
+    ActionState, Autopilot, ConfigPath, GameAction, GameConfig, GameRng, GameScreen, GameStatus,
+    InputBinding, InputBindings,
+};
 
 /// Bevy version string (hardcoded since bevy doesn't expose VERSION constant)
 const BEVY_VERSION: &str = "0.17";
@@ -30,6 +39,12 @@ pub struct DebugWindowState {
     time_since_update: f32,
     /// Status message for config operations
     config_status: Option<String>,
+    /// Action waiting for the next pressed input to be bound to it
+    rebinding: Option<GameAction>,
+    /// Draw hitboxes, the ground, jump arcs and obstacle thresholds over the game
+    pub show_gizmos: bool,
+    /// Run one simulation tick on the next frame, while time is paused
+    step_requested: bool,
 }
 
 impl Default for DebugWindowState {
@@ -41,6 +56,9 @@ impl Default for DebugWindowState {
             cached_frame_count: None,
             time_since_update: 0.0,
             config_status: None,
+            rebinding: None,
+            show_gizmos: false,
+            step_requested: false,
         }
     }
 }
@@ -52,18 +70,99 @@ impl Plugin for DebugPlugin {
         app.add_plugins(EguiPlugin::default())
             .add_plugins(FrameTimeDiagnosticsPlugin::default())
             .init_resource::<DebugWindowState>()
-            .add_systems(Update, toggle_debug_window)
+            .init_resource::<EguiWantsPointer>()
+            .add_systems(PreUpdate, sync_egui_wants_pointer.before(resolve_actions))
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_window,
+                    capture_rebinding,
+                    request_step_hotkey,
+                    step_fixed_tick.run_if(step_requested),
+                )
+                    .chain(),
+            )
+            // Drawn where entities are rendered, after interpolation moved them
+            .add_systems(
+                PostUpdate,
+                (draw_hitboxes, draw_ground_and_thresholds, draw_jump_arcs)
+                    .after(TransformSystems::Propagate)
+                    .run_if(gizmos_enabled),
+            )
             // Use EguiPrimaryContextPass for proper multi-pass mode input handling
             .add_systems(EguiPrimaryContextPass, show_debug_window);
     }
 }
 
-fn toggle_debug_window(input: Res<ButtonInput<KeyCode>>, mut state: ResMut<DebugWindowState>) {
-    if input.just_pressed(KeyCode::F1) {
+fn sync_egui_wants_pointer(mut contexts: EguiContexts, mut wants: ResMut<EguiWantsPointer>) {
+    wants.0 = egui_wants_pointer(&mut contexts);
+}
+
+fn toggle_debug_window(actions: Res<ActionState>, mut state: ResMut<DebugWindowState>) {
+    if actions.just_pressed(GameAction::ToggleDebug) {
         state.visible = !state.visible;
     }
 }
 
+/// [`GameAction::StepTick`] steps the simulation, but only while time is paused
+fn request_step_hotkey(
+    actions: Res<ActionState>,
+    virtual_time: Res<Time<Virtual>>,
+    mut state: ResMut<DebugWindowState>,
+) {
+    if actions.just_pressed(GameAction::StepTick) && virtual_time.is_paused() {
+        state.step_requested = true;
+    }
+}
+
+fn step_requested(state: Res<DebugWindowState>) -> bool {
+    state.step_requested
+}
+
+/// Run the requested tick, whichever way the game was paused
+fn step_fixed_tick(world: &mut World) {
+    world.resource_mut::<DebugWindowState>().step_requested = false;
+    step_one_tick(world);
+}
+
+/// Bind the first key, mouse or gamepad button pressed while rebinding
+fn capture_rebinding(
+    mut state: ResMut<DebugWindowState>,
+    mut bindings: ResMut<InputBindings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    egui_wants_pointer: Res<EguiWantsPointer>,
+) {
+    let Some(action) = state.rebinding else {
+        return;
+    };
+    let pressed = keyboard
+        .get_just_pressed()
+        .next()
+        .map(|key| InputBinding::Key(*key))
+        // Clicks on the debug window itself are not bindings
+        .or_else(|| {
+            mouse
+                .get_just_pressed()
+                .next()
+                .filter(|_| !egui_wants_pointer.0)
+                .map(|button| InputBinding::Mouse(*button))
+        })
+        .or_else(|| {
+            gamepads.iter().find_map(|gamepad| {
+                gamepad
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| InputBinding::Gamepad(*button))
+            })
+        });
+    if let Some(binding) = pressed {
+        bindings.bind(action, binding);
+        state.rebinding = None;
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn show_debug_window(
     mut contexts: EguiContexts,
@@ -75,6 +174,10 @@ fn show_debug_window(
     mut next_screen: ResMut<NextState<GameScreen>>,
     mut config: ResMut<GameConfig>,
     game_status: Res<GameStatus>,
+    rng: Res<GameRng>,
+    mut bindings: ResMut<InputBindings>,
+    mut autopilot: ResMut<Autopilot>,
+    config_path: Res<ConfigPath>,
 ) {
     if !state.visible {
         return;
@@ -147,6 +250,7 @@ fn show_debug_window(
 
             let is_paused = virtual_time.is_paused();
             ui.label(format!("Game Paused: {}", is_paused));
+            ui.label(format!("Run Seed: {}", rng.seed()));
 
             ui.horizontal(|ui| {
                 if ui
@@ -171,6 +275,44 @@ fn show_debug_window(
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.label("Time Scale:");
+                let mut time_scale = virtual_time.relative_speed();
+                if ui
+                    .add(
+                        egui::Slider::new(&mut time_scale, 0.1..=4.0)
+                            .logarithmic(true)
+                            .suffix("x"),
+                    )
+                    .changed()
+                {
+                    virtual_time.set_relative_speed(time_scale);
+                }
+                if ui.button("1x").clicked() {
+                    virtual_time.set_relative_speed(1.0);
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(is_paused, egui::Button::new("Step One Tick"))
+                    .clicked()
+                {
+                    state.step_requested = true;
+                }
+                ui.label("F2 while paused");
+            });
+
+            ui.checkbox(
+                &mut autopilot.enabled,
+                "Autopilot (keeps starting new runs)",
+            );
+
+            ui.checkbox(
+                &mut state.show_gizmos,
+                "Show hitboxes, ground, jump arcs and spawn thresholds",
+            );
+
             ui.separator();
             ui.heading("Game Config");
             ui.separator();
@@ -194,13 +336,56 @@ fn show_debug_window(
                     );
                 });
                 ui.horizontal(|ui| {
-                    ui.label("Jump Height:");
+                    ui.label("Duck Height:");
                     ui.add(
-                        egui::DragValue::new(&mut config.dino_jump_height)
-                            .range(50.0..=500.0)
+                        egui::DragValue::new(&mut config.dino_duck_height)
+                            .range(10.0..=300.0)
                             .speed(1.0),
                     );
                 });
+                ui.horizontal(|ui| {
+                    ui.label("Jump Velocity:");
+                    ui.add(
+                        egui::DragValue::new(&mut config.dino_jump_velocity)
+                            .range(100.0..=5000.0)
+                            .speed(10.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Gravity:");
+                    ui.add(
+                        egui::DragValue::new(&mut config.dino_gravity)
+                            .range(100.0..=30000.0)
+                            .speed(50.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Max Jump Hold:");
+                    ui.add(
+                        egui::DragValue::new(&mut config.dino_max_jump_hold)
+                            .range(0.0..=1.0)
+                            .speed(0.01)
+                            .suffix(" s"),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Jump Buffer:");
+                    ui.add(
+                        egui::DragValue::new(&mut config.jump_buffer_secs)
+                            .range(0.0..=0.5)
+                            .speed(0.01)
+                            .suffix(" s"),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Coyote Time:");
+                    ui.add(
+                        egui::DragValue::new(&mut config.coyote_time_secs)
+                            .range(0.0..=0.5)
+                            .speed(0.01)
+                            .suffix(" s"),
+                    );
+                });
                 ui.horizontal(|ui| {
                     ui.label("X Offset:");
                     ui.add(
@@ -231,6 +416,30 @@ fn show_debug_window(
                 });
             });
 
+            // Bird settings
+            ui.collapsing("Bird Settings", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Width:");
+                    ui.add(
+                        egui::DragValue::new(&mut config.bird_width)
+                            .range(10.0..=150.0)
+                            .speed(1.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Height:");
+                    ui.add(
+                        egui::DragValue::new(&mut config.bird_height)
+                            .range(10.0..=100.0)
+                            .speed(1.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Unlock Speed:");
+                    ui.add(egui::DragValue::new(&mut config.bird_unlock_speed).speed(10.0));
+                });
+            });
+
             // Ground settings
             let half_height = game_status.window_height / 2.0;
             ui.collapsing("Ground Settings", |ui| {
@@ -244,15 +453,62 @@ fn show_debug_window(
                 });
             });
 
+            // Seed settings
+            ui.collapsing("Seed Settings", |ui| {
+                let mut fixed_seed = config.seed.is_some();
+                ui.checkbox(&mut fixed_seed, "Fixed seed (applies to the next run)");
+                if fixed_seed != config.seed.is_some() {
+                    config.seed = fixed_seed.then(|| rng.seed());
+                }
+                if let Some(seed) = config.seed.as_mut() {
+                    ui.horizontal(|ui| {
+                        ui.label("Seed:");
+                        ui.add(egui::DragValue::new(seed));
+                    });
+                }
+                ui.checkbox(
+                    &mut config.ghost,
+                    "Race the ghost of the best run (applies to the next run)",
+                );
+            });
+
+            // Input bindings, click a binding to remove it
+            ui.collapsing("Input Bindings", |ui| {
+                for action in GameAction::ALL {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(format!("{:?}:", action));
+                        for binding in bindings.get(action).to_vec() {
+                            if ui.button(binding.to_string()).clicked() {
+                                bindings.unbind(action, binding);
+                            }
+                        }
+                        if state.rebinding == Some(action) {
+                            ui.label("Press a key or button...");
+                            if ui.button("Cancel").clicked() {
+                                state.rebinding = None;
+                            }
+                        } else if ui.button("+").clicked() {
+                            state.rebinding = Some(action);
+                        }
+                    });
+                }
+            });
+
             ui.separator();
 
-            // Config export/import buttons
+            // Config export/import buttons, input bindings are kept next to the config
             ui.horizontal(|ui| {
                 if ui.button("Export Config").clicked() {
-                    match config.save_to_file() {
+                    match config
+                        .save_to_file(&config_path.0)
+                        .and_then(|()| bindings.save_to_file(config_path.bindings_file()))
+                    {
                         Ok(()) => {
-                            state.config_status =
-                                Some("Config exported to game_config.json".to_string());
+                            state.config_status = Some(format!(
+                                "Config exported to {} and {}",
+                                config_path.0.display(),
+                                config_path.bindings_file().display()
+                            ));
                         }
                         Err(e) => {
                             state.config_status = Some(format!("Export failed: {}", e));
@@ -261,13 +517,18 @@ fn show_debug_window(
                 }
 
                 if ui.button("Load Config").clicked() {
-                    let loaded = GameConfig::load_from_file();
-                    *config = loaded;
-                    state.config_status = Some("Config loaded from game_config.json".to_string());
+                    *config = GameConfig::load_from_file(&config_path.0);
+                    *bindings = InputBindings::load_from_file(config_path.bindings_file());
+                    state.config_status = Some(format!(
+                        "Config loaded from {} and {}",
+                        config_path.0.display(),
+                        config_path.bindings_file().display()
+                    ));
                 }
 
                 if ui.button("Reset to Default").clicked() {
                     *config = GameConfig::default();
+                    *bindings = InputBindings::default();
                     state.config_status = Some("Config reset to defaults".to_string());
                 }
             });
@@ -0,0 +1,90 @@
+//! Spatial overlay for the debug window: what collides, where the ground is,
+//! how far a jump reaches and where obstacles come and go.
+
+use bevy::{color::palettes::css, math::bounding::BoundingVolume, prelude::*};
+
+use crate::{
+    components::{Dino, Hitboxes},
+    debug::DebugWindowState,
+    dino::{jump_altitude, jump_duration},
+    ghost::Ghost,
+    obstacle::{min_obstacle_gap, world_speed},
+    GameConfig, GameStatus,
+};
+
+/// Points sampled along each predicted jump arc
+const JUMP_ARC_POINTS: usize = 48;
+
+/// Whether the overlay is switched on in the debug window
+pub(crate) fn gizmos_enabled(state: Res<DebugWindowState>) -> bool {
+    state.show_gizmos
+}
+
+/// Outline every hitbox where the entity is rendered this frame
+pub(crate) fn draw_hitboxes(
+    mut gizmos: Gizmos,
+    query: Query<(&GlobalTransform, &Sprite, &Hitboxes, Has<Dino>)>,
+) {
+    for (transform, sprite, hitboxes, is_dino) in query.iter() {
+        let (scale, _, translation) = transform.to_scale_rotation_translation();
+        let size = sprite.custom_size.unwrap_or_default() * scale.xy();
+        let color = if is_dino { css::LIME } else { css::RED };
+        for aabb in hitboxes.aabbs(translation.xy(), size) {
+            gizmos.rect_2d(aabb.center(), aabb.half_size() * 2.0, color);
+        }
+    }
+}
+
+/// Ground line, the obstacle spawn and despawn edges, and the earliest point the last
+/// obstacle has to pass before the next group can spawn
+pub(crate) fn draw_ground_and_thresholds(
+    mut gizmos: Gizmos,
+    status: Res<GameStatus>,
+    config: Res<GameConfig>,
+) {
+    let half_width = status.window_width / 2.0;
+    let half_height = status.window_height / 2.0;
+    gizmos.line_2d(
+        Vec2::new(-half_width, config.ground_y_pos),
+        Vec2::new(half_width, config.ground_y_pos),
+        css::BLUE,
+    );
+
+    let vertical = |x: f32| (Vec2::new(x, -half_height), Vec2::new(x, half_height));
+    let (start, end) = vertical(half_width);
+    gizmos.line_2d(start, end, css::ORANGE);
+    let (start, end) = vertical(-half_width);
+    gizmos.line_2d(start, end, css::PURPLE);
+    let (start, end) = vertical(half_width - min_obstacle_gap(&status, &config));
+    gizmos.line_2d(start, end, css::YELLOW);
+}
+
+/// Path of the dino's feet relative to the obstacles, for a held and a tapped jump
+/// from where the dino stands. Tree height is marked for comparison.
+pub(crate) fn draw_jump_arcs(
+    mut gizmos: Gizmos,
+    dinos: Query<&GlobalTransform, (With<Dino>, Without<Ghost>)>,
+    status: Res<GameStatus>,
+    config: Res<GameConfig>,
+) {
+    let speed = world_speed(&status, config.tree_width);
+    for transform in dinos.iter() {
+        let origin = Vec2::new(transform.translation().x, config.ground_y_pos);
+        for (held, color) in [(true, css::AQUA), (false, css::FUCHSIA)] {
+            let duration = jump_duration(&config, held);
+            let arc = (0..=JUMP_ARC_POINTS)
+                .map(|i| duration * i as f32 / JUMP_ARC_POINTS as f32)
+                .map(|secs| {
+                    let altitude = jump_altitude(&config, secs, held).max(0.0);
+                    origin + Vec2::new(speed * secs, altitude)
+                });
+            gizmos.linestrip_2d(arc, color);
+        }
+        let tree_top = origin.y + config.tree_height;
+        gizmos.line_2d(
+            Vec2::new(origin.x, tree_top),
+            Vec2::new(origin.x + speed * jump_duration(&config, true), tree_top),
+            css::GRAY,
+        );
+    }
+}
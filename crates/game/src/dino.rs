@@ -1,56 +1,59 @@
 use bevy::{
-    app::{Plugin, Update},
-    asset::AssetServer,
-    ecs::schedule::IntoScheduleConfigs,
-    input::ButtonInput,
-    prelude::{
-        Assets, Commands, KeyCode, MouseButton, Query, Res, ResMut, Touches, Transform, With,
+    app::{FixedUpdate, Plugin},
+    ecs::{
+        message::{Message, MessageWriter},
+        schedule::IntoScheduleConfigs,
     },
+    prelude::{Commands, Has, Query, Res, Transform, With},
     sprite::Sprite,
-    state::{
-        condition::in_state,
-        state::{OnEnter, OnExit},
-    },
-    time::{Time, Virtual},
+    state::state::{OnEnter, OnExit},
+    time::Time,
 };
-use bevy_egui::EguiContexts;
-use bevy_kira_audio::{Audio, AudioControl, AudioInstance};
 
 use crate::{
-    components::Dino, utils::cleanup_component, utils::egui_wants_pointer, DinoJumpMusic,
-    GameConfig, GameScreen, GameStatus,
+    components::{Dino, Hitboxes},
+    ghost::Ghost,
+    interpolation::TickTranslation,
+    utils::cleanup_component,
+    FixedActionState, GameAction, GameConfig, GameStatus, InGame, SimulationSystems,
 };
 
+/// How fast a dino drops back to the ground when ducking mid-air, in pixels per second
+const FAST_FALL_SPEED: f32 = 1500.0;
+
+/// Sent when a dino leaves the ground
+#[derive(Message, Debug, Clone, Copy)]
+pub struct DinoJumped;
+
+/// Sent when a dino is back on the ground after a jump
+#[derive(Message, Debug, Clone, Copy)]
+pub struct DinoLanded;
+
 pub struct DinoPlugin;
 
 impl Plugin for DinoPlugin {
     fn build(&self, app: &mut bevy::app::App) {
-        app.add_systems(
-            Update,
-            (
-                dino_pos_fix_system,
-                dino_jump_system,
-                dino_jump_animation,
-                update_dino_sprite_from_config,
+        app.add_message::<DinoJumped>()
+            .add_message::<DinoLanded>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    dino_pos_fix_system,
+                    dino_duck_system,
+                    dino_jump_system,
+                    update_dino_sprite_from_config,
+                    dino_jump_physics,
+                )
+                    .chain()
+                    .in_set(SimulationSystems::Move),
             )
-                .run_if(in_state(GameScreen::PlayScreen)),
-        )
-        .add_systems(OnEnter(GameScreen::PlayScreen), setup_dino)
-        .add_systems(
-            OnExit(GameScreen::PlayScreen),
-            (cleanup_component::<Dino>, clean_dino_jump_music),
-        );
+            .add_systems(OnEnter(InGame), setup_dino)
+            .add_systems(OnExit(InGame), cleanup_component::<Dino>);
     }
 }
 
-fn setup_dino(mut commands: Commands, assert_server: Res<AssetServer>, config: Res<GameConfig>) {
-    let sound = assert_server.load("Jump.ogg");
-    commands.insert_resource(DinoJumpMusic(sound));
-    commands.spawn(Dino::new(&config));
-}
-
-fn clean_dino_jump_music(mut commands: Commands) {
-    commands.remove_resource::<DinoJumpMusic>();
+fn setup_dino(mut commands: Commands, config: Res<GameConfig>) {
+    commands.spawn((Dino::new(&config), TickTranslation::default()));
 }
 
 fn dino_pos_fix_system(
@@ -66,92 +69,167 @@ fn dino_pos_fix_system(
     }
 }
 
-/// Dino will jump when user press space, w, Up, k, or left mouse button
-#[allow(clippy::too_many_arguments)]
-fn dino_jump_system(
-    mut dino_query: Query<&mut Dino>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mouse: Res<ButtonInput<MouseButton>>,
-    touch: Res<Touches>,
-    time: Res<Time<Virtual>>,
-    sound: Res<DinoJumpMusic>,
-    audio: Res<Audio>,
-    mut contexts: EguiContexts,
+/// Dino ducks while [`GameAction::Duck`] is held.
+/// Ducking mid-air cuts the jump short with a fast fall.
+fn dino_duck_system(
+    mut dino_query: Query<(&mut Dino, Option<&Ghost>)>,
+    actions: Res<FixedActionState>,
 ) {
-    if time.is_paused() {
-        return;
+    for (mut dino, ghost) in dino_query.iter_mut() {
+        let duck = ghost
+            .map_or(&**actions, Ghost::actions)
+            .pressed(GameAction::Duck);
+        if duck && dino.in_air {
+            dino.jump_hold_secs = None;
+            dino.velocity = dino.velocity.min(-FAST_FALL_SPEED);
+        }
+        dino.is_ducking = duck && !dino.in_air;
     }
+}
 
-    // Only process mouse/touch if egui doesn't want the input
-    let pointer_input = if egui_wants_pointer(&mut contexts) {
-        false
-    } else {
-        mouse.just_pressed(MouseButton::Left) || touch.any_just_pressed()
-    };
-
-    if keyboard.just_pressed(KeyCode::Space)
-        || keyboard.just_pressed(KeyCode::KeyW)
-        || keyboard.just_pressed(KeyCode::ArrowUp)
-        || keyboard.just_pressed(KeyCode::KeyK)
-        || pointer_input
-    {
-        for mut dino in dino_query.iter_mut() {
-            if dino.in_air_start_time.is_some() {
-                continue;
-            } else {
-                let handle = audio.play(sound.clone()).handle();
-                dino.jump_sound = Some(handle);
-                dino.in_air_start_time = Some(*time);
+/// Dino jumps on [`GameAction::Jump`].
+/// Holding it keeps the jump going up for a while, releasing it cuts the jump short.
+/// A press that can't jump yet is buffered for `GameConfig::jump_buffer_secs`, so a jump
+/// pressed right before landing or while getting up from a duck still fires.
+fn dino_jump_system(
+    mut dino_query: Query<(&mut Dino, Option<&Ghost>)>,
+    actions: Res<FixedActionState>,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut jumped: MessageWriter<DinoJumped>,
+) {
+    for (mut dino, ghost) in dino_query.iter_mut() {
+        let actions = ghost.map_or(&**actions, Ghost::actions);
+        let pressed = actions.just_pressed(GameAction::Jump);
+        let held = actions.pressed(GameAction::Jump);
+        if dino.in_air && !held {
+            dino.jump_hold_secs = None;
+        }
+        if pressed {
+            dino.buffered_jump_secs = Some(0.0);
+        }
+        let Some(buffered) = dino.buffered_jump_secs else {
+            continue;
+        };
+        if dino.can_jump(&config) {
+            dino.buffered_jump_secs = None;
+            dino.in_air = true;
+            dino.is_jumping = true;
+            dino.velocity = config.dino_jump_velocity;
+            // A buffered jump only gets the extra height if the button is still down
+            dino.jump_hold_secs = held.then_some(0.0);
+            if ghost.is_none() {
+                jumped.write(DinoJumped);
             }
+        } else {
+            let buffered = buffered + time.delta_secs();
+            dino.buffered_jump_secs = (buffered <= config.jump_buffer_secs).then_some(buffered);
         }
     }
 }
 
-fn dino_jump_animation(
-    time: Res<Time<Virtual>>,
-    mut query: Query<(&mut Transform, &mut Dino)>,
-    mut audio_instances: ResMut<Assets<AudioInstance>>,
+/// Move airborne dinos: no gravity while the jump is held, then a ballistic fall.
+/// A dino that left the ground without jumping just falls.
+fn dino_jump_physics(
+    time: Res<Time>,
+    mut query: Query<(&mut Transform, &mut Dino, Has<Ghost>)>,
     config: Res<GameConfig>,
+    mut landed: MessageWriter<DinoLanded>,
 ) {
-    if time.is_paused() {
-        return;
-    }
-    // Base Y position is ground position plus half the dino height
-    let base_y = config.ground_y_pos + config.dino_height / 2.0;
-    for (mut transform, mut dino) in query.iter_mut() {
-        if let Some(start_time) = dino.in_air_start_time {
-            let elapsed = time.elapsed() - start_time.elapsed();
-            // Over
-            let y = if elapsed.as_millis() > 500 {
-                if let Some(handle) = dino.jump_sound.take() {
-                    if let Some(instance) = audio_instances.get_mut(&handle) {
-                        instance.pause(Default::default());
-                    }
+    let dt = time.delta_secs();
+    for (mut transform, mut dino, is_ghost) in query.iter_mut() {
+        if dino.in_air {
+            dino.airborne_secs += dt;
+            match dino.jump_hold_secs {
+                Some(held) if held < config.dino_max_jump_hold => {
+                    dino.jump_hold_secs = Some(held + dt);
                 }
-                dino.in_air_start_time = None;
-                base_y
-            } else {
-                let x = elapsed.as_millis() as f64 / 500.0 * std::f64::consts::PI;
-                let x = x as f32;
-                x.sin() * config.dino_jump_height + base_y
-            };
-            transform.translation.y = y;
-        } else {
-            // When not jumping, keep dino at base Y position (follows ground)
-            transform.translation.y = base_y;
+                _ => {
+                    dino.jump_hold_secs = None;
+                    dino.velocity -= config.dino_gravity * dt;
+                }
+            }
+            dino.altitude += dino.velocity * dt;
+            if dino.altitude <= 0.0 {
+                dino.in_air = false;
+                dino.is_jumping = false;
+                dino.airborne_secs = 0.0;
+                dino.altitude = 0.0;
+                dino.velocity = 0.0;
+                if !is_ghost {
+                    landed.write(DinoLanded);
+                }
+            }
         }
+        // Ground position plus half the dino height, so its feet stay on the ground
+        transform.translation.y = config.ground_y_pos + dino.altitude + dino.height(&config) / 2.0;
     }
 }
 
-/// Update dino sprite size based on config changes in real-time
+/// Longest a dino can stay in the air, with the jump held as long as it counts
+pub(crate) fn max_air_time(config: &GameConfig) -> f32 {
+    config.dino_max_jump_hold + 2.0 * config.dino_jump_velocity / config.dino_gravity
+}
+
+/// Height of a dino's feet `secs` into a jump, held as long as it counts or just tapped.
+/// Ignores landing, so it goes below zero once the jump is over.
+pub(crate) fn jump_altitude(config: &GameConfig, secs: f32, held: bool) -> f32 {
+    let velocity = config.dino_jump_velocity;
+    let hold = if held {
+        secs.min(config.dino_max_jump_hold)
+    } else {
+        0.0
+    };
+    let falling = secs - hold;
+    velocity * hold + velocity * falling - config.dino_gravity * falling * falling / 2.0
+}
+
+/// How long a jump held as long as it counts, or just tapped, keeps the dino in the air
+pub(crate) fn jump_duration(config: &GameConfig, held: bool) -> f32 {
+    let velocity = config.dino_jump_velocity;
+    let hold = if held { config.dino_max_jump_hold } else { 0.0 };
+    let hold_height = velocity * hold;
+    let falling = (velocity
+        + (velocity * velocity + 2.0 * config.dino_gravity * hold_height).sqrt())
+        / config.dino_gravity;
+    hold + falling
+}
+
+/// How long the highest possible jump keeps the dino's feet above `height`
+pub(crate) fn max_jump_time_above(config: &GameConfig, height: f32) -> f32 {
+    let velocity = config.dino_jump_velocity;
+    let hold_height = velocity * config.dino_max_jump_hold;
+    let peak = hold_height + velocity * velocity / (2.0 * config.dino_gravity);
+    if height >= peak {
+        return 0.0;
+    }
+    // Falling from the peak down to `height` takes as long as the ballistic rise from it
+    let ballistic = (2.0 * (peak - height) / config.dino_gravity).sqrt();
+    if height <= hold_height {
+        let held_above = config.dino_max_jump_hold - height / velocity;
+        held_above + velocity / config.dino_gravity + ballistic
+    } else {
+        2.0 * ballistic
+    }
+}
+
+/// Update dino sprite size and hitboxes based on config changes and ducking in real-time
 fn update_dino_sprite_from_config(
-    mut query: Query<&mut Sprite, With<Dino>>,
+    mut query: Query<(&mut Sprite, &mut Hitboxes, &Dino)>,
     config: Res<GameConfig>,
 ) {
-    for mut sprite in query.iter_mut() {
-        let new_size = bevy::math::Vec2::new(config.dino_width, config.dino_height);
+    for (mut sprite, mut hitboxes, dino) in query.iter_mut() {
+        let new_size = bevy::math::Vec2::new(config.dino_width, dino.height(&config));
         if sprite.custom_size != Some(new_size) {
             sprite.custom_size = Some(new_size);
         }
+        let new_hitboxes = if dino.is_ducking {
+            &config.dino_duck_hitboxes
+        } else {
+            &config.dino_hitboxes
+        };
+        if hitboxes.0 != *new_hitboxes {
+            hitboxes.0 = new_hitboxes.clone();
+        }
     }
 }
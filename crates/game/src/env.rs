@@ -0,0 +1,104 @@
+//! Reinforcement learning environment around the real game, stepped one tick at a time.
+
+use bevy::{ecs::system::SystemState, prelude::*};
+
+use crate::{
+    agent::observe,
+    app::{AppType, Game},
+    components::{Dino, Obstacle},
+    ghost::Ghost,
+    AgentAction, AgentInput, GameConfig, GameScreen, GameStatus, Observation, RunProgress, RunSeed,
+};
+
+type ObservedState = SystemState<(
+    Query<'static, 'static, (&'static Dino, &'static Transform, &'static Sprite), Without<Ghost>>,
+    Query<'static, 'static, (&'static Transform, &'static Sprite), With<Obstacle>>,
+    Res<'static, GameStatus>,
+    Res<'static, GameConfig>,
+)>;
+
+/// The game as a gym-style environment: [`DinoEnv::reset`] starts a run from a seed,
+/// every [`DinoEnv::step`] plays exactly one simulation tick with the agent's action.
+/// It runs the same systems as the game, without a window, renderer or audio.
+pub struct DinoEnv {
+    game: Game,
+    observed: ObservedState,
+    /// Unrounded score at the end of the last step, the reward is what it grew by
+    points: f64,
+}
+
+impl DinoEnv {
+    pub fn new(config: GameConfig) -> Self {
+        let mut game = Game::init(AppType::Headless);
+        game.world_mut().insert_resource(config);
+        game.world_mut().insert_resource(AgentInput::default());
+        // Let the startup systems run once before the first reset
+        game.update();
+        let observed = SystemState::new(game.world_mut());
+        Self {
+            game,
+            observed,
+            points: 0.0,
+        }
+    }
+
+    /// Start a new run from `seed`, with the agent not holding anything
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        let world = self.game.world_mut();
+        world.insert_resource(RunSeed(Some(seed)));
+        world.insert_resource(AgentInput::default());
+        // Leaving the run first, so a run still going is thrown away too
+        world
+            .resource_mut::<NextState<GameScreen>>()
+            .set(GameScreen::StartScreen);
+        self.game.update();
+        self.game
+            .world_mut()
+            .resource_mut::<NextState<GameScreen>>()
+            .set(GameScreen::PlayScreen);
+        self.game.update();
+        self.points = self.game.world().resource::<RunProgress>().points;
+        self.observation()
+    }
+
+    /// Play one tick holding `action`.
+    /// Returns what the agent sees next, the score gained during the tick, and whether the run
+    /// is over. Once it is, stepping changes nothing until the next [`DinoEnv::reset`].
+    pub fn step(&mut self, action: AgentAction) -> (Observation, f32, bool) {
+        if !self.done() {
+            self.game.world_mut().insert_resource(AgentInput(action));
+            self.game.update();
+        }
+        let points = self.game.world().resource::<RunProgress>().points;
+        let reward = (points - self.points) as f32;
+        self.points = points;
+        (self.observation(), reward, self.done())
+    }
+
+    /// Whether the dino hit an obstacle, or no run was started yet
+    pub fn done(&self) -> bool {
+        *self.game.world().resource::<State<GameScreen>>().get() != GameScreen::PlayScreen
+    }
+
+    /// Score of the current run
+    pub fn score(&self) -> u64 {
+        self.game.world().resource::<GameStatus>().score
+    }
+
+    /// What the agent sees of the current tick
+    pub fn observation(&mut self) -> Observation {
+        let world = self.game.world();
+        let (dinos, obstacles, status, config) = self.observed.get(world);
+        observe(&dinos, &obstacles, &status, &config).unwrap_or_default()
+    }
+
+    pub fn world(&self) -> &World {
+        self.game.world()
+    }
+}
+
+impl Default for DinoEnv {
+    fn default() -> Self {
+        Self::new(GameConfig::default())
+    }
+}
@@ -1,12 +1,12 @@
-use bevy::math::bounding::Aabb2d;
-use bevy::math::bounding::IntersectsVolume;
+use std::time::Duration;
+
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::math::bounding::{Aabb2d, AabbCast2d, BoundingVolume, IntersectsVolume};
 use bevy::prelude::*;
-use bevy_egui::EguiContexts;
 
-use crate::components::Dino;
-use crate::components::Tree;
-use crate::utils::egui_wants_pointer;
-use crate::GameScreen;
+use crate::components::{Dino, Hitboxes, Obstacle, PreviousPosition};
+use crate::ghost::Ghost;
+use crate::{ActionState, GameAction, GameScreen, SimulationSystems};
 
 pub struct GameLogicPlugin;
 
@@ -15,55 +15,120 @@ impl Plugin for GameLogicPlugin {
         app.add_systems(
             FixedUpdate,
             (
-                dino_touched_tree.run_if(in_state(GameScreen::PlayScreen)),
-                back_to_play_while_game_over.run_if(in_state(GameScreen::GameOverScreen)),
+                remember_positions.in_set(SimulationSystems::Input),
+                dino_touched_tree.in_set(SimulationSystems::Collide),
             ),
+        )
+        .add_systems(OnEnter(GameScreen::GameOverScreen), rumble_gamepads)
+        .add_systems(
+            Update,
+            back_to_play_while_game_over.run_if(in_state(GameScreen::GameOverScreen)),
         );
     }
 }
 
+/// Remember where everything is before the tick moves it, for the swept collision tests
+fn remember_positions(mut query: Query<(&Transform, &mut PreviousPosition)>) {
+    for (transform, mut previous) in query.iter_mut() {
+        previous.0 = Some(transform.translation.xy());
+    }
+}
+
+/// Where something is, where it was at the start of the tick and what of it collides
+type Collider = (
+    &'static Transform,
+    &'static Sprite,
+    &'static Hitboxes,
+    &'static PreviousPosition,
+);
+
+/// Check every hitbox of every dino against every hitbox of every obstacle.
+/// The boxes are swept along the way they moved this tick, so nothing fast slips through.
 fn dino_touched_tree(
-    dino_query: Query<(&Transform, &Sprite), With<Dino>>,
-    tree_query: Query<(&Sprite, &Transform), With<Tree>>,
+    dino_query: Query<(Collider, Has<Ghost>), With<Dino>>,
+    obstacle_query: Query<Collider, With<Obstacle>>,
     mut next_screen: ResMut<NextState<GameScreen>>,
 ) {
-    for ((dino_transform, dino_sprite), (tree_sprite, tree_transform)) in
-        dino_query.iter().zip(tree_query.iter())
-    {
-        let aabb_dino = Aabb2d::new(
-            dino_transform.translation.xy(),
-            dino_sprite.custom_size.unwrap() / 2.0 / dino_transform.scale.xy(),
-        );
-
-        let aabb_tree = Aabb2d::new(
-            tree_transform.translation.xy(),
-            tree_sprite.custom_size.unwrap() / 2.0 / tree_transform.scale.xy(),
-        );
+    for ((dino_transform, dino_sprite, dino_hitboxes, dino_previous), ghost) in dino_query.iter() {
+        // Ghosts pass through everything
+        if ghost {
+            continue;
+        }
+        let dino_moved = moved_this_tick(dino_transform, dino_previous);
+        let dino_boxes: Vec<Aabb2d> = dino_hitboxes
+            .aabbs(
+                dino_transform.translation.xy(),
+                sprite_size(dino_sprite, dino_transform),
+            )
+            .collect();
 
-        if aabb_tree.intersects(&aabb_dino) {
-            next_screen.set(GameScreen::GameOverScreen);
+        for (obstacle_transform, obstacle_sprite, obstacle_hitboxes, obstacle_previous) in
+            obstacle_query.iter()
+        {
+            let relative = moved_this_tick(obstacle_transform, obstacle_previous) - dino_moved;
+            let hit = obstacle_hitboxes
+                .aabbs(
+                    obstacle_transform.translation.xy(),
+                    sprite_size(obstacle_sprite, obstacle_transform),
+                )
+                .any(|obstacle| {
+                    dino_boxes
+                        .iter()
+                        .any(|dino| swept_hit(*dino, obstacle, relative))
+                });
+            if hit {
+                next_screen.set(GameScreen::GameOverScreen);
+                return;
+            }
         }
     }
 }
 
+/// Size of a sprite on screen, what collisions and observations measure
+pub(crate) fn sprite_size(sprite: &Sprite, transform: &Transform) -> Vec2 {
+    sprite.custom_size.unwrap_or_default() * transform.scale.xy()
+}
+
+fn moved_this_tick(transform: &Transform, previous: &PreviousPosition) -> Vec2 {
+    previous
+        .0
+        .map_or(Vec2::ZERO, |previous| transform.translation.xy() - previous)
+}
+
+/// Whether `obstacle` touched `dino` at any point of the tick.
+/// Seen from the dino only the obstacle moves, by `relative`, ending where it is now.
+fn swept_hit(dino: Aabb2d, obstacle: Aabb2d, relative: Vec2) -> bool {
+    let Ok(direction) = Dir2::new(relative) else {
+        return obstacle.intersects(&dino);
+    };
+    let start = obstacle.center() - relative;
+    let shape = Aabb2d::new(Vec2::ZERO, obstacle.half_size());
+    AabbCast2d::new(shape, start, direction, relative.length()).intersects(&dino)
+}
+
+/// How long gamepads rumble after the dino hit an obstacle
+const CRASH_RUMBLE: Duration = Duration::from_millis(300);
+
+/// Shake every connected gamepad, does nothing on pads without rumble motors
+fn rumble_gamepads(
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut rumble: MessageWriter<GamepadRumbleRequest>,
+) {
+    for gamepad in gamepads.iter() {
+        rumble.write(GamepadRumbleRequest::Add {
+            duration: CRASH_RUMBLE,
+            intensity: GamepadRumbleIntensity::MAX,
+            gamepad,
+        });
+    }
+}
+
 fn back_to_play_while_game_over(
-    cur_screen: Res<State<GameScreen>>,
+    actions: Res<ActionState>,
     mut next_screen: ResMut<NextState<GameScreen>>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mouse: Res<ButtonInput<MouseButton>>,
-    touch: Res<Touches>,
-    mut contexts: EguiContexts,
 ) {
-    // Only process mouse/touch if egui doesn't want the input
-    let pointer_input = if egui_wants_pointer(&mut contexts) {
-        false
-    } else {
-        touch.any_just_pressed() || mouse.just_pressed(MouseButton::Left)
-    };
-
-    if *cur_screen == GameScreen::GameOverScreen
-        && (keyboard.just_pressed(KeyCode::Space) || pointer_input)
-    {
+    if actions.just_pressed(GameAction::Confirm) {
+        info!("Restart Game");
         next_screen.set(GameScreen::PlayScreen);
     }
 }
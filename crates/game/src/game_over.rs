@@ -1,26 +1,27 @@
 use bevy::prelude::*;
-use bevy_egui::EguiContexts;
 
-use crate::utils::egui_wants_pointer;
-use crate::{utils::cleanup_component, GameScreen};
+use crate::high_score::record_high_score;
+use crate::{utils::cleanup_component, GameScreen, HighScores};
 
 pub struct GameOverPlugin;
 
 impl Plugin for GameOverPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_systems(OnEnter(GameScreen::GameOverScreen), show_game_over_info)
-            .add_systems(FixedUpdate, restart_game_by_space)
-            .add_systems(
-                OnExit(GameScreen::GameOverScreen),
-                cleanup_component::<GameOverTextUI>,
-            );
+        app.add_systems(
+            OnEnter(GameScreen::GameOverScreen),
+            show_game_over_info.after(record_high_score),
+        )
+        .add_systems(
+            OnExit(GameScreen::GameOverScreen),
+            cleanup_component::<GameOverTextUI>,
+        );
     }
 }
 
 #[derive(Component)]
 pub struct GameOverTextUI;
 
-fn show_game_over_info(mut commands: Commands) {
+fn show_game_over_info(mut commands: Commands, high_scores: Option<Res<HighScores>>) {
     info!("Showing Game Over");
     commands
         .spawn((
@@ -51,28 +52,25 @@ fn show_game_over_info(mut commands: Commands) {
                 },
                 TextColor(Color::BLACK),
             ));
+            let Some(high_scores) = high_scores else {
+                return;
+            };
+            for (rank, entry) in high_scores.entries.iter().enumerate() {
+                parent.spawn((
+                    Text::new(format!(
+                        "{:>2}. {:05}  speed {:<4} {:>6.1}s  {}",
+                        rank + 1,
+                        entry.score,
+                        entry.max_speed,
+                        entry.duration_secs,
+                        entry.date()
+                    )),
+                    TextFont {
+                        font_size: 24.0,
+                        ..Default::default()
+                    },
+                    TextColor(Color::BLACK),
+                ));
+            }
         });
 }
-
-fn restart_game_by_space(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mouse: Res<ButtonInput<MouseButton>>,
-    touches: Res<Touches>,
-    cur_screen: Res<State<GameScreen>>,
-    mut next_state: ResMut<NextState<GameScreen>>,
-    mut contexts: EguiContexts,
-) {
-    // Only process mouse/touch if egui doesn't want the input
-    let pointer_input = if egui_wants_pointer(&mut contexts) {
-        false
-    } else {
-        mouse.just_pressed(MouseButton::Left) || touches.any_just_pressed()
-    };
-
-    if *cur_screen == GameScreen::GameOverScreen
-        && (keyboard.just_pressed(KeyCode::Space) || pointer_input)
-    {
-        info!("Restart Game");
-        next_state.set(GameScreen::StartScreen);
-    }
-}
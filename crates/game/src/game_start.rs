@@ -1,9 +1,9 @@
 use bevy::prelude::*;
-use bevy_egui::EguiContexts;
 
-use crate::components::{Dino, DINO_DEFAULT_COLOR, DINO_TOUCHED_COLOR};
-use crate::utils::egui_wants_pointer;
-use crate::{utils::cleanup_component, GameConfig, GameScreen, GameStatus};
+use crate::components::Dino;
+use crate::{
+    utils::cleanup_component, ActionState, GameAction, GameConfig, GameScreen, GameStatus,
+};
 
 pub struct GameStartPlugin;
 
@@ -19,8 +19,8 @@ impl Plugin for GameStartPlugin {
                     .run_if(in_state(GameScreen::StartScreen)),
             )
             .add_systems(
-                FixedUpdate,
-                enter_play_by_space.run_if(in_state(GameScreen::StartScreen)),
+                Update,
+                enter_play_on_confirm.run_if(in_state(GameScreen::StartScreen)),
             )
             .add_systems(OnExit(GameScreen::StartScreen), cleanup_component::<Dino>);
     }
@@ -59,61 +59,12 @@ fn update_start_dino_sprite_from_config(
     }
 }
 
-fn enter_play_by_space(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mouse: Res<ButtonInput<MouseButton>>,
-    touches: Res<Touches>,
+fn enter_play_on_confirm(
+    actions: Res<ActionState>,
     mut next_screen: ResMut<NextState<GameScreen>>,
-    mut dino_query: Query<(&mut Dino, &mut Sprite)>,
-    mut contexts: EguiContexts,
 ) {
-    // Only process mouse/touch if egui doesn't want the input
-    let touch_input = if egui_wants_pointer(&mut contexts) {
-        false
-    } else {
-        touches.any_just_pressed()
-    };
-
-    let mouse_input = if egui_wants_pointer(&mut contexts) {
-        false
-    } else {
-        mouse.just_pressed(MouseButton::Left)
-    };
-
-    // If touch input detected, turn dino red (only one dino expected on start screen)
-    if touch_input {
-        if let Ok((mut dino, mut sprite)) = dino_query.single_mut() {
-            if !dino.is_touched {
-                dino.is_touched = true;
-                sprite.color = DINO_TOUCHED_COLOR;
-                info!("Dino touched - turning red");
-            }
-        }
-    }
-
-    // Space key resets dino to default color (begin state) or starts game
-    if keyboard.just_pressed(KeyCode::Space) {
-        if let Ok((mut dino, mut sprite)) = dino_query.single_mut() {
-            if dino.is_touched {
-                // Reset dino to default state
-                dino.is_touched = false;
-                sprite.color = DINO_DEFAULT_COLOR;
-                info!("Dino reset to default state");
-            } else {
-                // Start playing
-                info!("Start Playing");
-                next_screen.set(GameScreen::PlayScreen);
-            }
-        } else {
-            // No dino found, just start playing
-            info!("Start Playing");
-            next_screen.set(GameScreen::PlayScreen);
-        }
-    }
-
-    // Mouse click starts the game directly (not touch, which turns dino red first)
-    if mouse_input {
-        info!("Start Playing via mouse click");
+    if actions.just_pressed(GameAction::Confirm) {
+        info!("Start Playing");
         next_screen.set(GameScreen::PlayScreen);
     }
 }
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{Dino, DINO_GHOST_COLOR},
+    interpolation::TickTranslation,
+    ActionState, BestReplay, GameConfig, InGame, Replay, ReplayPlayback, SimulationSystems,
+};
+
+/// A dino replaying an earlier run next to the live one.
+/// It has its own actions, never collides and makes no sound.
+#[derive(Component, Debug)]
+pub struct Ghost {
+    playback: ReplayPlayback,
+    actions: ActionState,
+}
+
+impl Ghost {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            playback: ReplayPlayback::new(replay),
+            actions: ActionState::default(),
+        }
+    }
+
+    /// Actions driving the ghost this frame
+    pub fn actions(&self) -> &ActionState {
+        &self.actions
+    }
+}
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(InGame), spawn_ghost)
+            .add_systems(FixedUpdate, step_ghosts.in_set(SimulationSystems::Input));
+    }
+}
+
+/// Seed of the run the ghost replays, when racing it is on
+pub(crate) fn ghost_seed(config: &GameConfig, best: Option<&BestReplay>) -> Option<u64> {
+    let replay = best?.0.as_ref()?;
+    config.ghost.then_some(replay.seed)
+}
+
+fn spawn_ghost(mut commands: Commands, config: Res<GameConfig>, best: Res<BestReplay>) {
+    let Some(replay) = best.0.as_ref().filter(|_| config.ghost) else {
+        return;
+    };
+    let (dino, sprite, mut transform) = Dino::new_with_color(&config, DINO_GHOST_COLOR);
+    // Behind the live dino
+    transform.translation.z = -1.0;
+    commands.spawn((
+        Ghost::new(replay.clone()),
+        dino,
+        sprite,
+        transform,
+        TickTranslation::default(),
+    ));
+}
+
+/// Feed each ghost its recorded actions, it leaves once its run is over
+fn step_ghosts(mut commands: Commands, mut ghosts: Query<(Entity, &mut Ghost)>) {
+    for (entity, mut ghost) in ghosts.iter_mut() {
+        if ghost.playback.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let ghost = ghost.as_mut();
+        ghost.playback.step(&mut ghost.actions);
+    }
+}
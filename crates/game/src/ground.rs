@@ -8,15 +8,15 @@ use bevy::{
     transform::components::Transform,
 };
 
-use crate::{components::Ground, utils::cleanup_component, GameConfig, GameScreen, GameStatus};
+use crate::{components::Ground, utils::cleanup_component, GameConfig, GameStatus, InGame};
 
 pub struct GroundPlugin;
 
 impl Plugin for GroundPlugin {
     fn build(&self, app: &mut bevy::app::App) {
-        app.add_systems(OnEnter(GameScreen::PlayScreen), setup_ground)
+        app.add_systems(OnEnter(InGame), setup_ground)
             .add_systems(Update, (update_ground, update_ground_from_config))
-            .add_systems(OnExit(GameScreen::PlayScreen), cleanup_component::<Ground>);
+            .add_systems(OnExit(InGame), cleanup_component::<Ground>);
     }
 }
 
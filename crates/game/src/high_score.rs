@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{storage, GameScreen, GameStatus, HighScoreEntry, HighScores, RunProgress};
+
+/// Keep the best runs across sessions
+pub struct HighScorePlugin;
+
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HighScores::load())
+            .add_systems(OnEnter(GameScreen::GameOverScreen), record_high_score);
+    }
+}
+
+pub(crate) fn record_high_score(
+    status: Res<GameStatus>,
+    progress: Res<RunProgress>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    if status.score == 0 {
+        return;
+    }
+    let entry = HighScoreEntry {
+        score: status.score,
+        max_speed: progress.max_speed,
+        duration_secs: progress.duration_secs,
+        timestamp: storage::unix_timestamp(),
+    };
+    let Some(rank) = high_scores.insert(entry) else {
+        return;
+    };
+    info!("New high score #{} with {}", rank + 1, status.score);
+    if let Err(e) = high_scores.save() {
+        warn!("Failed to save high scores: {}", e);
+    }
+}
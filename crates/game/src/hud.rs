@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use crate::{utils::cleanup_component, GameStatus, InGame, SessionHighScore};
+
+/// Digits the scores are zero-padded to
+const SCORE_DIGITS: usize = 5;
+
+/// Every time the score passes a multiple of this, it flashes
+const FLASH_EVERY: u64 = 100;
+
+/// Seconds the score stays shown or hidden while flashing
+const FLASH_INTERVAL: f32 = 0.25;
+
+/// Times the score is shown or hidden during one flash, four blinks
+const FLASH_TOGGLES: u32 = 8;
+
+const SCORE_COLOR: Color = Color::srgb(0.33, 0.33, 0.33);
+const HIGH_SCORE_COLOR: Color = Color::srgb(0.55, 0.55, 0.55);
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(InGame), setup_score_hud)
+            .add_systems(
+                Update,
+                (update_high_score_text, update_score_text).run_if(in_state(InGame)),
+            )
+            .add_systems(OnExit(InGame), cleanup_component::<ScoreHud>);
+    }
+}
+
+/// Root node of the score display
+#[derive(Component)]
+pub struct ScoreHud;
+
+#[derive(Component)]
+struct HighScoreText;
+
+/// Current score, flashing the last reached milestone like the original game
+#[derive(Component, Default)]
+struct ScoreText {
+    milestone: u64,
+    toggles_left: u32,
+    timer: Timer,
+}
+
+fn format_score(score: u64) -> String {
+    format!("{score:0SCORE_DIGITS$}")
+}
+
+fn setup_score_hud(mut commands: Commands, high_score: Res<SessionHighScore>) {
+    commands
+        .spawn((
+            ScoreHud,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(16.0),
+                right: Val::Px(24.0),
+                column_gap: Val::Px(24.0),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                HighScoreText,
+                Text::new(format!("HI {}", format_score(high_score.0))),
+                TextFont {
+                    font_size: 32.0,
+                    ..Default::default()
+                },
+                TextColor(HIGH_SCORE_COLOR),
+            ));
+            parent.spawn((
+                ScoreText::default(),
+                Text::new(format_score(0)),
+                TextFont {
+                    font_size: 32.0,
+                    ..Default::default()
+                },
+                TextColor(SCORE_COLOR),
+            ));
+        });
+}
+
+fn update_high_score_text(
+    high_score: Res<SessionHighScore>,
+    mut query: Query<&mut Text, With<HighScoreText>>,
+) {
+    if !high_score.is_changed() {
+        return;
+    }
+    for mut text in query.iter_mut() {
+        text.0 = format!("HI {}", format_score(high_score.0));
+    }
+}
+
+fn update_score_text(
+    time: Res<Time<Virtual>>,
+    status: Res<GameStatus>,
+    mut query: Query<(&mut Text, &mut ScoreText, &mut Visibility)>,
+) {
+    for (mut text, mut score_text, mut visibility) in query.iter_mut() {
+        let milestone = status.score / FLASH_EVERY * FLASH_EVERY;
+        if milestone > score_text.milestone {
+            score_text.milestone = milestone;
+            score_text.toggles_left = FLASH_TOGGLES;
+            score_text.timer = Timer::from_seconds(FLASH_INTERVAL, TimerMode::Repeating);
+        }
+
+        if score_text.toggles_left == 0 {
+            text.0 = format_score(status.score);
+            *visibility = Visibility::Inherited;
+            continue;
+        }
+
+        // The reached milestone is shown while flashing instead of the running score
+        text.0 = format_score(score_text.milestone);
+        score_text.timer.tick(time.delta());
+        for _ in 0..score_text.timer.times_finished_this_tick() {
+            score_text.toggles_left = score_text.toggles_left.saturating_sub(1);
+            visibility.toggle_inherited_hidden();
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+};
+
+use bevy::{
+    input::{gamepad::GamepadButton, InputSystems},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{utils::EguiWantsPointer, ConfigPath, GameScreen};
+
+/// How far a touch has to move down before it counts as a swipe, in logical pixels
+const SWIPE_DOWN_DISTANCE: f32 = 30.0;
+
+/// What the player wants to do, whatever device they use for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GameAction {
+    Jump,
+    Duck,
+    Pause,
+    /// Start, restart or resume the game
+    Confirm,
+    ToggleDebug,
+    /// Run a single simulation tick while time is paused
+    StepTick,
+}
+
+impl GameAction {
+    pub const ALL: [GameAction; 6] = [
+        GameAction::Jump,
+        GameAction::Duck,
+        GameAction::Pause,
+        GameAction::Confirm,
+        GameAction::ToggleDebug,
+        GameAction::StepTick,
+    ];
+}
+
+/// A physical input that can trigger a [`GameAction`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// Any finger on the screen
+    Touch,
+    /// A finger moving down the screen
+    SwipeDown,
+    Gamepad(GamepadButton),
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputBinding::Key(key) => write!(f, "{:?}", key),
+            InputBinding::Mouse(button) => write!(f, "Mouse {:?}", button),
+            InputBinding::Touch => write!(f, "Touch"),
+            InputBinding::SwipeDown => write!(f, "Swipe Down"),
+            InputBinding::Gamepad(button) => write!(f, "Gamepad {:?}", button),
+        }
+    }
+}
+
+/// Which inputs trigger each action, saved next to [`crate::GameConfig`]
+#[derive(Debug, Clone, PartialEq, Resource, Serialize, Deserialize)]
+pub struct InputBindings {
+    pub actions: BTreeMap<GameAction, Vec<InputBinding>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use InputBinding::*;
+        let actions = BTreeMap::from([
+            (
+                GameAction::Jump,
+                vec![
+                    Key(KeyCode::Space),
+                    Key(KeyCode::KeyW),
+                    Key(KeyCode::ArrowUp),
+                    Key(KeyCode::KeyK),
+                    Mouse(MouseButton::Left),
+                    Touch,
+                    Gamepad(GamepadButton::South),
+                    Gamepad(GamepadButton::DPadUp),
+                ],
+            ),
+            (
+                GameAction::Duck,
+                vec![
+                    Key(KeyCode::ArrowDown),
+                    Key(KeyCode::KeyS),
+                    Key(KeyCode::KeyJ),
+                    SwipeDown,
+                    Gamepad(GamepadButton::DPadDown),
+                ],
+            ),
+            (
+                GameAction::Pause,
+                vec![Key(KeyCode::Escape), Gamepad(GamepadButton::Start)],
+            ),
+            (
+                GameAction::Confirm,
+                vec![
+                    Key(KeyCode::Space),
+                    Mouse(MouseButton::Left),
+                    Touch,
+                    Gamepad(GamepadButton::South),
+                    // Start pauses during a run and starts one from the start and game over screens
+                    Gamepad(GamepadButton::Start),
+                ],
+            ),
+            (GameAction::ToggleDebug, vec![Key(KeyCode::F1)]),
+            (GameAction::StepTick, vec![Key(KeyCode::F2)]),
+        ]);
+        Self { actions }
+    }
+}
+
+impl InputBindings {
+    /// Default bindings file name
+    pub const BINDINGS_FILE: &'static str = "input_bindings.json";
+
+    /// Load bindings from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Export bindings to a JSON string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Load bindings from file, returns default if file doesn't exist or is invalid.
+    /// Actions missing from the file keep their default bindings.
+    pub fn load_from_file(path: impl AsRef<std::path::Path>) -> Self {
+        let mut bindings = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| Self::from_json(&content).ok())
+            .unwrap_or_default();
+        for (action, defaults) in Self::default().actions {
+            bindings.actions.entry(action).or_insert(defaults);
+        }
+        bindings
+    }
+
+    /// Save bindings to file
+    pub fn save_to_file(&self, path: impl AsRef<std::path::Path>) -> Result<(), std::io::Error> {
+        let json = self
+            .to_json()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        std::fs::write(path, json)
+    }
+
+    pub fn get(&self, action: GameAction) -> &[InputBinding] {
+        self.actions.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Add `binding` to `action`, does nothing if it's already bound to it
+    pub fn bind(&mut self, action: GameAction, binding: InputBinding) {
+        let bindings = self.actions.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn unbind(&mut self, action: GameAction, binding: InputBinding) {
+        if let Some(bindings) = self.actions.get_mut(&action) {
+            bindings.retain(|b| *b != binding);
+        }
+    }
+}
+
+/// Actions held this frame, resolved once from every bound input before `Update`
+#[derive(Debug, Default, Clone, Resource)]
+pub struct ActionState {
+    pressed: HashSet<GameAction>,
+    previous: HashSet<GameAction>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: GameAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    /// Held this frame but not the one before
+    pub fn just_pressed(&self, action: GameAction) -> bool {
+        self.pressed.contains(&action) && !self.previous.contains(&action)
+    }
+
+    /// Held the frame before but not this one
+    pub fn just_released(&self, action: GameAction) -> bool {
+        !self.pressed.contains(&action) && self.previous.contains(&action)
+    }
+
+    /// Every action held
+    pub fn held(&self) -> impl Iterator<Item = GameAction> + '_ {
+        self.pressed.iter().copied()
+    }
+
+    /// Held the frame before
+    pub fn was_pressed(&self, action: GameAction) -> bool {
+        self.previous.contains(&action)
+    }
+
+    /// Override `action` for this frame, for input that doesn't come from a device
+    pub fn set(&mut self, action: GameAction, pressed: bool, was_pressed: bool) {
+        for (set, held) in [
+            (&mut self.pressed, pressed),
+            (&mut self.previous, was_pressed),
+        ] {
+            if held {
+                set.insert(action);
+            } else {
+                set.remove(&action);
+            }
+        }
+    }
+
+    /// Start a new frame with `pressed` held
+    pub fn update(&mut self, pressed: impl IntoIterator<Item = GameAction>) {
+        self.previous = std::mem::take(&mut self.pressed);
+        self.pressed.extend(pressed);
+    }
+}
+
+/// Actions held during a simulation tick, what the gameplay systems on `FixedUpdate` read.
+/// A press between two ticks is kept for the next one, even if released before it.
+#[derive(Debug, Default, Clone, Resource, Deref, DerefMut)]
+pub struct FixedActionState {
+    #[deref]
+    state: ActionState,
+    /// Held at some point since the last tick
+    pending: HashSet<GameAction>,
+}
+
+pub struct GameInputPlugin;
+
+impl Plugin for GameInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionState>()
+            .init_resource::<FixedActionState>()
+            .insert_resource(InputBindings::load_from_file(
+                ConfigPath::default().bindings_file(),
+            ))
+            .add_systems(
+                PreUpdate,
+                (resolve_actions, latch_fixed_actions)
+                    .chain()
+                    .after(InputSystems),
+            )
+            .add_systems(FixedPreUpdate, advance_fixed_actions)
+            .add_systems(OnEnter(GameScreen::PlayScreen), reset_fixed_actions);
+    }
+}
+
+/// Turn the raw inputs into [`ActionState`].
+/// Mouse and touch are ignored while egui uses the pointer.
+pub(crate) fn resolve_actions(
+    bindings: Res<InputBindings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepads: Query<&Gamepad>,
+    egui_wants_pointer: Res<EguiWantsPointer>,
+    mut actions: ResMut<ActionState>,
+) {
+    let pointer_free = !egui_wants_pointer.0;
+    // Also count presses released within the same frame, so quick taps aren't lost
+    let is_held = |binding: &InputBinding| match *binding {
+        InputBinding::Key(key) => keyboard.pressed(key) || keyboard.just_pressed(key),
+        InputBinding::Mouse(button) => {
+            pointer_free && (mouse.pressed(button) || mouse.just_pressed(button))
+        }
+        InputBinding::Touch => {
+            pointer_free && (touches.iter().next().is_some() || touches.any_just_pressed())
+        }
+        // Screen coordinates grow downwards
+        InputBinding::SwipeDown => {
+            pointer_free
+                && touches
+                    .iter()
+                    .any(|t| t.position().y - t.start_position().y > SWIPE_DOWN_DISTANCE)
+        }
+        InputBinding::Gamepad(button) => gamepads
+            .iter()
+            .any(|gamepad| gamepad.pressed(button) || gamepad.just_pressed(button)),
+    };
+    let held = bindings
+        .actions
+        .iter()
+        .filter(|(_, bindings)| bindings.iter().any(is_held))
+        .map(|(action, _)| *action);
+    actions.update(held);
+}
+
+fn latch_fixed_actions(actions: Res<ActionState>, mut fixed: ResMut<FixedActionState>) {
+    fixed.pending.extend(actions.held());
+}
+
+/// Presses made on another screen stay there, like the one that started or resumed the run.
+/// Whatever is still held counts as held from before, not as a new press.
+fn reset_fixed_actions(actions: Res<ActionState>, mut fixed: ResMut<FixedActionState>) {
+    fixed.pending.clear();
+    fixed.state = ActionState::default();
+    fixed.state.update(actions.held());
+}
+
+/// Start a new tick with everything held since the last one, or still held now
+fn advance_fixed_actions(actions: Res<ActionState>, mut fixed: ResMut<FixedActionState>) {
+    let fixed = fixed.as_mut();
+    let held: Vec<GameAction> = fixed.pending.drain().chain(actions.held()).collect();
+    fixed.state.update(held);
+}
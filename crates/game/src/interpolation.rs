@@ -0,0 +1,61 @@
+//! Smooth rendering between simulation ticks.
+//! The simulation owns `Transform` during `FixedUpdate`; for the frames in between it is
+//! blended from the last two ticks, and put back before the next tick runs.
+
+use bevy::{prelude::*, transform::TransformSystems};
+
+/// Translation of a simulated entity at the last two ticks
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct TickTranslation {
+    previous: Vec3,
+    current: Vec3,
+    /// Recorded at least one tick, new entities render where they were spawned until then
+    ticked: bool,
+}
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedFirst, restore_tick_translation)
+            .add_systems(FixedLast, record_tick_translation)
+            .add_systems(
+                PostUpdate,
+                interpolate_translation.before(TransformSystems::Propagate),
+            );
+    }
+}
+
+/// Hand the simulation back the translation it left off with
+fn restore_tick_translation(mut query: Query<(&mut Transform, &TickTranslation)>) {
+    for (mut transform, tick) in query.iter_mut() {
+        if tick.ticked {
+            transform.translation = tick.current;
+        }
+    }
+}
+
+fn record_tick_translation(mut query: Query<(&Transform, &mut TickTranslation)>) {
+    for (transform, mut tick) in query.iter_mut() {
+        tick.previous = if tick.ticked {
+            tick.current
+        } else {
+            transform.translation
+        };
+        tick.current = transform.translation;
+        tick.ticked = true;
+    }
+}
+
+/// Render between the last two ticks, by how far time got past the last one
+fn interpolate_translation(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &TickTranslation)>,
+) {
+    let blend = fixed_time.overstep_fraction();
+    for (mut transform, tick) in query.iter_mut() {
+        if tick.ticked {
+            transform.translation = tick.previous.lerp(tick.current, blend);
+        }
+    }
+}
@@ -1,28 +1,67 @@
+mod agent;
+mod agent_stdio;
 pub mod app;
+mod audio;
+mod autopilot;
 mod camera;
+pub mod cli;
 pub mod components;
 mod debug;
+mod debug_gizmos;
 mod dino;
+mod env;
 mod game_control;
 mod game_logic;
 mod game_over;
 mod game_start;
+mod ghost;
 mod ground;
+mod high_score;
+mod hud;
+mod input;
+mod interpolation;
+mod obstacle;
+mod policy;
+mod render_to_image;
+mod replay;
 mod resources;
+mod rng;
+mod score;
 mod screens;
-mod tree;
+mod simulation;
+mod storage;
 pub mod utils;
 mod windows_handler;
 
+pub use agent::{AgentAction, AgentInput, AgentPlugin, Observation};
+pub use agent_stdio::{AgentCommand, AgentFrame, AgentIo, AgentStdioPlugin};
+pub use audio::{AudioMuted, GameAudioPlugin};
+pub use autopilot::{Autopilot, AutopilotPlugin};
 pub use camera::setup_2d_camera;
 pub use debug::DebugPlugin;
-pub use dino::DinoPlugin;
+pub use dino::{DinoJumped, DinoLanded, DinoPlugin};
+pub use env::DinoEnv;
 pub use game_control::GameControlPlugin;
 pub use game_logic::GameLogicPlugin;
 pub use game_over::GameOverPlugin;
 pub use game_start::GameStartPlugin;
+pub use ghost::{Ghost, GhostPlugin};
 pub use ground::GroundPlugin;
+pub use high_score::HighScorePlugin;
+pub use hud::{HudPlugin, ScoreHud};
+pub use input::{
+    ActionState, FixedActionState, GameAction, GameInputPlugin, InputBinding, InputBindings,
+};
+pub use interpolation::{InterpolationPlugin, TickTranslation};
+pub use obstacle::{ObstacleCleared, ObstaclePlugin};
+pub use policy::{NeuralPolicy, PolicyPlugin, POLICY_INPUTS};
+pub use render_to_image::{RenderTargetImage, RenderToImageConfig, RenderToImagePlugin};
+pub use replay::{
+    BestReplay, LastReplayPlugin, Replay, ReplayEvent, ReplayPlayback, ReplayPlugin, ReplayRecorder,
+};
 pub use resources::*;
-pub use screens::GameScreen;
-pub use tree::TreePlugin;
+pub use rng::{GameRng, GameRngPlugin, RunSeed};
+pub use score::ScorePlugin;
+pub use screens::{GameScreen, InGame};
+pub use simulation::{step_one_tick, SimulationPlugin, SimulationSystems};
 pub use windows_handler::*;
@@ -1,8 +1,80 @@
+use std::path::Path;
+
 use bevy::app::AppExit;
-use dinosaur_game::app::{AppType, Game};
+use dinosaur_game::{
+    app::{AppType, Game},
+    cli::{CliArgs, HELP},
+    NeuralPolicy, Replay,
+};
+
+/// Report a file given on the command line that cannot be used, the same way a bad flag is
+fn cannot_load(flag: &str, path: &Path, e: std::io::Error) -> ! {
+    eprintln!("error: cannot load {} {}: {}", flag, path.display(), e);
+    std::process::exit(2);
+}
 
 fn main() {
-    let game = Game::init(AppType::Normal);
+    let cli = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, HELP);
+            std::process::exit(2);
+        }
+    };
+    if cli.help {
+        print!("{}", HELP);
+        return;
+    }
+
+    // Check the files before opening a window, a typo should not flash one up
+    let replay = cli.replay.as_deref().map(|path| {
+        Replay::load_from_file(path).unwrap_or_else(|e| cannot_load("--replay", path, e))
+    });
+    let policy = cli.policy.as_deref().map(|path| {
+        NeuralPolicy::load_from_file(path).unwrap_or_else(|e| cannot_load("--policy", path, e))
+    });
+
+    // Agents get the bare simulation, one tick per line on stdin
+    let app_type = if cli.agent_stdio {
+        AppType::Headless
+    } else {
+        AppType::Normal
+    };
+    let mut game = Game::init(app_type);
+    if cli.agent_stdio {
+        game = game.with_agent_stdio();
+    }
+    if let Some(path) = cli.config {
+        game = game.with_config_file(path);
+    }
+    if let Some(seed) = cli.seed {
+        game = game.with_seed(seed);
+    }
+    // A replay brings its own seed and config
+    if let Some(replay) = replay {
+        game = game.with_replay(replay);
+    }
+    if let Some(policy) = policy {
+        game = game.with_policy(policy);
+    }
+    if cli.autopilot {
+        game = game.with_autopilot();
+    }
+    if let Some((width, height)) = cli.window_size {
+        game = game.with_window_size(width, height);
+    }
+    if cli.fullscreen {
+        game = game.with_fullscreen();
+    }
+    if let Some(screen) = cli.screen {
+        game = game.with_start_screen(screen);
+    }
+    if cli.mute {
+        game = game.with_muted_audio();
+    }
+    if cli.debug {
+        game = game.with_debug_window();
+    }
     let exit = game.run();
     match exit {
         AppExit::Success => {}
@@ -0,0 +1,285 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    app::{FixedUpdate, Plugin},
+    ecs::{
+        message::{Message, MessageWriter},
+        query::With,
+        schedule::IntoScheduleConfigs,
+    },
+    math::{Vec2, Vec3},
+    prelude::{Commands, Entity, Query, Res, ResMut, Resource, Without},
+    sprite::Sprite,
+    state::state::{OnEnter, OnExit},
+    time::Time,
+    transform::components::Transform,
+};
+use rand::Rng;
+
+use crate::{
+    components::{Bird, BirdWing, Hitboxes, Obstacle, Tree, BIRD_FLIGHT_LEVELS},
+    dino::{max_air_time, max_jump_time_above},
+    utils::cleanup_component,
+    GameConfig, GameRng, GameStatus, InGame, SimulationSystems, SpeedControlInfo,
+};
+
+/// Patterns kept ready in the spawner queue
+const QUEUED_PATTERNS: usize = 4;
+/// Largest gap between two groups, as a multiple of [`min_obstacle_gap`]
+const MAX_GAP_FACTOR: f32 = 3.0;
+/// Largest group of trees placed side by side
+const MAX_GROUP_TREES: u8 = 3;
+/// Chance for a group to be a bird instead, once birds are unlocked
+const BIRD_CHANCE: f64 = 0.3;
+
+/// Sent when an obstacle made it past the dino and leaves the screen
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ObstacleCleared;
+
+/// A group of obstacles waiting in the spawner queue
+#[derive(Debug, Clone, Copy)]
+struct ObstaclePattern {
+    /// Empty space in front of the group, as a multiple of [`min_obstacle_gap`].
+    /// Kept relative so the gap still fits when the speed changed since it was queued.
+    gap_factor: f32,
+    /// Trees placed side by side, capped by [`max_group_trees`] when spawned
+    trees: u8,
+    /// Flight level of a bird replacing the trees, ignored until birds are unlocked
+    bird: Option<usize>,
+}
+
+impl ObstaclePattern {
+    fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            gap_factor: rng.gen_range(1.0..MAX_GAP_FACTOR),
+            trees: rng.gen_range(1..=MAX_GROUP_TREES),
+            bird: rng
+                .gen_bool(BIRD_CHANCE)
+                .then(|| rng.gen_range(0..BIRD_FLIGHT_LEVELS.len())),
+        }
+    }
+}
+
+/// Upcoming obstacles, spawned at the right edge once the previous group is far enough
+#[derive(Debug, Default, Resource)]
+pub struct ObstacleSpawner {
+    queue: VecDeque<ObstaclePattern>,
+}
+
+pub struct ObstaclePlugin;
+
+impl Plugin for ObstaclePlugin {
+    fn build(&self, app: &mut bevy::app::App) {
+        app.add_message::<ObstacleCleared>()
+            .init_resource::<ObstacleSpawner>()
+            .add_systems(OnEnter(InGame), reset_spawner)
+            .add_systems(
+                FixedUpdate,
+                (spawn_obstacles, move_obstacles, despawn_passed_obstacles)
+                    .chain()
+                    .in_set(SimulationSystems::Move),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    update_tree_sprite_from_config,
+                    update_bird_sprite_from_config,
+                    flap_bird_wings,
+                )
+                    .after(SimulationSystems::Move)
+                    .before(SimulationSystems::Collide),
+            )
+            .add_systems(OnExit(InGame), cleanup_component::<Obstacle>);
+    }
+}
+
+fn reset_spawner(mut spawner: ResMut<ObstacleSpawner>) {
+    *spawner = ObstacleSpawner::default();
+}
+
+/// How fast obstacles move towards the dino, in pixels per second
+pub(crate) fn world_speed(status: &GameStatus, obstacle_width: f32) -> f32 {
+    let more_hard_speed = (status.speed as f32).log2();
+    status.window_width / 3.0 + (obstacle_width / 2.0) * more_hard_speed
+}
+
+/// Smallest space between two groups: the longest jump plus the dino itself,
+/// so the dino can land and take off again before the next group arrives
+pub(crate) fn min_obstacle_gap(status: &GameStatus, config: &GameConfig) -> f32 {
+    world_speed(status, config.tree_width) * max_air_time(config) + config.dino_width
+}
+
+/// Most trees a group can hold while a single held jump still clears it
+fn max_group_trees(status: &GameStatus, config: &GameConfig) -> u8 {
+    let above_trees = max_jump_time_above(config, config.tree_height);
+    let clear_width = world_speed(status, config.tree_width) * above_trees - config.dino_width;
+    ((clear_width / config.tree_width).floor() as u8).clamp(1, MAX_GROUP_TREES)
+}
+
+fn spawn_obstacles(
+    mut commands: Commands,
+    mut spawner: ResMut<ObstacleSpawner>,
+    obstacles: Query<(&Transform, &Sprite), With<Obstacle>>,
+    status: Res<GameStatus>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<GameRng>,
+) {
+    while spawner.queue.len() < QUEUED_PATTERNS {
+        spawner
+            .queue
+            .push_back(ObstaclePattern::random(rng.as_mut()));
+    }
+
+    let next_pattern = spawner.queue[0];
+    let bird = next_pattern
+        .bird
+        .filter(|_| status.speed >= config.bird_unlock_speed);
+    let first_width = if bird.is_some() {
+        config.bird_width
+    } else {
+        config.tree_width
+    };
+    // The first group starts right at the edge, later ones keep their gap to the last obstacle
+    let right_edge = status.window_width / 2.0;
+    let last_obstacle_end = obstacles
+        .iter()
+        .map(|(transform, sprite)| {
+            let width = sprite.custom_size.map(|s| s.x).unwrap_or(config.tree_width);
+            transform.translation.x + width / 2.0
+        })
+        .reduce(f32::max);
+    let start = match last_obstacle_end {
+        Some(end) => end + next_pattern.gap_factor * min_obstacle_gap(&status, &config),
+        None => right_edge,
+    };
+    if start > right_edge {
+        return;
+    }
+
+    spawner.queue.pop_front();
+    if let Some(flight_level) = bird {
+        let x = start + first_width / 2.0;
+        commands
+            .spawn(Bird::new(&config, flight_level, x))
+            .with_child(BirdWing::new(&config));
+        return;
+    }
+    let y = config.ground_y_pos + config.tree_height / 2.0;
+    let trees = next_pattern.trees.min(max_group_trees(&status, &config));
+    for i in 0..trees {
+        let x = start + (f32::from(i) + 0.5) * config.tree_width;
+        commands.spawn(Tree::new(&config, Vec3::new(x, y, 0.0)));
+    }
+}
+
+fn move_obstacles(
+    mut obstacles: Query<&mut Transform, With<Obstacle>>,
+    time: Res<Time>,
+    status: Res<GameStatus>,
+    config: Res<GameConfig>,
+) {
+    // Every obstacle moves at the same pace, so the gaps between them hold
+    let speed = world_speed(&status, config.tree_width);
+    for mut transform in obstacles.iter_mut() {
+        transform.translation.x -= time.delta_secs() * speed;
+    }
+}
+
+fn despawn_passed_obstacles(
+    mut commands: Commands,
+    obstacles: Query<(Entity, &Transform, &Sprite), With<Obstacle>>,
+    mut status: ResMut<GameStatus>,
+    mut speed_control_info: ResMut<SpeedControlInfo>,
+    config: Res<GameConfig>,
+    mut cleared: MessageWriter<ObstacleCleared>,
+) {
+    let left_edge = -status.window_width / 2.0;
+    for (entity, transform, sprite) in obstacles.iter() {
+        let width = sprite.custom_size.map(|s| s.x).unwrap_or(config.tree_width);
+        if transform.translation.x + width / 2.0 < left_edge {
+            commands.entity(entity).despawn();
+            update_game_speed(&mut status, &mut speed_control_info);
+            cleared.write(ObstacleCleared);
+        }
+    }
+}
+
+fn update_game_speed(status: &mut GameStatus, info: &mut SpeedControlInfo) {
+    if status.speed < info.max_game_speed {
+        let new_speed = status.speed.saturating_add(info.speed_increment);
+        info.speed_increment = info.speed_increment.saturating_add(info.speed_increment);
+        info.max_game_speed = info.max_game_speed.saturating_sub(info.speed_increment);
+        status.speed = if new_speed >= info.max_game_speed {
+            info.max_game_speed
+        } else {
+            new_speed
+        };
+    }
+}
+
+/// Update tree sprite size, hitboxes and Y position based on config changes in real-time
+fn update_tree_sprite_from_config(
+    mut query: Query<(&mut Sprite, &mut Hitboxes, &mut Transform), With<Tree>>,
+    config: Res<GameConfig>,
+) {
+    // Tree Y position is ground position plus half the tree height
+    let expected_y = config.ground_y_pos + config.tree_height / 2.0;
+    for (mut sprite, mut hitboxes, mut transform) in query.iter_mut() {
+        if hitboxes.0 != config.tree_hitboxes {
+            hitboxes.0 = config.tree_hitboxes.clone();
+        }
+        let new_size = bevy::math::Vec2::new(config.tree_width, config.tree_height);
+        if sprite.custom_size != Some(new_size) {
+            sprite.custom_size = Some(new_size);
+        }
+        // Update y position to keep tree on ground
+        if transform.translation.y != expected_y {
+            transform.translation.y = expected_y;
+        }
+    }
+}
+
+/// Update bird and wing sizes, bird hitboxes and Y positions based on config changes in real-time
+fn update_bird_sprite_from_config(
+    mut birds: Query<(&Bird, &mut Sprite, &mut Hitboxes, &mut Transform), Without<BirdWing>>,
+    mut wings: Query<(&BirdWing, &mut Sprite, &mut Transform), Without<Bird>>,
+    config: Res<GameConfig>,
+) {
+    let bird_size = Vec2::new(config.bird_width, config.bird_height);
+    for (bird, mut sprite, mut hitboxes, mut transform) in birds.iter_mut() {
+        if hitboxes.0 != config.bird_hitboxes {
+            hitboxes.0 = config.bird_hitboxes.clone();
+        }
+        if sprite.custom_size != Some(bird_size) {
+            sprite.custom_size = Some(bird_size);
+        }
+        let expected_y = bird.y_pos(&config);
+        if transform.translation.y != expected_y {
+            transform.translation.y = expected_y;
+        }
+    }
+    let wing_size = BirdWing::size(&config);
+    for (wing, mut sprite, mut transform) in wings.iter_mut() {
+        if sprite.custom_size != Some(wing_size) {
+            sprite.custom_size = Some(wing_size);
+        }
+        let expected_y = wing.y_offset(&config);
+        if transform.translation.y != expected_y {
+            transform.translation.y = expected_y;
+        }
+    }
+}
+
+/// Two-frame flap, the wing jumps between above and below the body
+fn flap_bird_wings(
+    mut wings: Query<(&mut BirdWing, &mut Transform)>,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+) {
+    for (mut wing, mut transform) in wings.iter_mut() {
+        if wing.flap.tick(time.delta()).just_finished() {
+            wing.up = !wing.up;
+            transform.translation.y = wing.y_offset(&config);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! A small neural network playing the game, evolved by the `dino-trainer` binary.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent::{apply_agent_input, observe},
+    components::{Dino, Obstacle},
+    ghost::Ghost,
+    AgentAction, AgentInput, GameConfig, GameStatus, Observation, SimulationSystems,
+};
+
+/// Numbers the network sees of an [`Observation`]
+pub const POLICY_INPUTS: usize = 10;
+
+/// Actions the network picks from, by highest output
+const POLICY_ACTIONS: [AgentAction; 3] = [AgentAction::Run, AgentAction::Jump, AgentAction::Duck];
+
+/// A network with one hidden layer, its genome is the flat list of weights.
+/// Every neuron has a bias, stored right after its input weights.
+#[derive(Debug, Clone, PartialEq, Resource, Serialize, Deserialize)]
+pub struct NeuralPolicy {
+    pub hidden: usize,
+    pub weights: Vec<f32>,
+}
+
+impl NeuralPolicy {
+    /// How many weights a network with `hidden` neurons has
+    pub const fn weight_count(hidden: usize) -> usize {
+        hidden * (POLICY_INPUTS + 1) + POLICY_ACTIONS.len() * (hidden + 1)
+    }
+
+    /// A network with every weight zero, it never does anything but run
+    pub fn zeroed(hidden: usize) -> Self {
+        Self {
+            hidden,
+            weights: vec![0.0; Self::weight_count(hidden)],
+        }
+    }
+
+    /// Scale the observation to roughly -1..1 so no input drowns out the others
+    fn inputs(observation: &Observation) -> [f32; POLICY_INPUTS] {
+        let flag = |on: bool| if on { 1.0 } else { 0.0 };
+        [
+            flag(observation.obstacle_ahead),
+            observation.obstacle_distance / 1000.0,
+            observation.obstacle_width / 100.0,
+            observation.obstacle_height / 100.0,
+            observation.obstacle_altitude / 100.0,
+            observation.dino_altitude / 100.0,
+            observation.dino_velocity / 1000.0,
+            flag(observation.dino_in_air),
+            flag(observation.dino_ducking),
+            observation.world_speed / 1000.0,
+        ]
+    }
+
+    /// What the network does about `observation`
+    pub fn act(&self, observation: &Observation) -> AgentAction {
+        let inputs = Self::inputs(observation);
+        let (hidden_weights, output_weights) =
+            self.weights.split_at(self.hidden * (POLICY_INPUTS + 1));
+        let hidden: Vec<f32> = hidden_weights
+            .chunks_exact(POLICY_INPUTS + 1)
+            .map(|neuron| neuron_output(neuron, &inputs).tanh())
+            .collect();
+        let outputs = output_weights
+            .chunks_exact(self.hidden + 1)
+            .map(|neuron| neuron_output(neuron, &hidden));
+        // Ties go to the first action, so a blank network just runs
+        let (best, _) = outputs
+            .enumerate()
+            .fold((0, f32::NEG_INFINITY), |best, (i, output)| {
+                if output > best.1 {
+                    (i, output)
+                } else {
+                    best
+                }
+            });
+        POLICY_ACTIONS[best]
+    }
+
+    /// Load a network from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, std::io::Error> {
+        let policy: Self = serde_json::from_str(json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        if policy.weights.len() != Self::weight_count(policy.hidden) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "expected {} weights for {} hidden neurons, got {}",
+                    Self::weight_count(policy.hidden),
+                    policy.hidden,
+                    policy.weights.len()
+                ),
+            ));
+        }
+        Ok(policy)
+    }
+
+    /// Export the network to a JSON string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn load_from_file(path: impl AsRef<std::path::Path>) -> Result<Self, std::io::Error> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn save_to_file(&self, path: impl AsRef<std::path::Path>) -> Result<(), std::io::Error> {
+        let json = self
+            .to_json()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Weighted sum of `inputs` plus the bias stored last in `weights`
+fn neuron_output(weights: &[f32], inputs: &[f32]) -> f32 {
+    let (bias, weights) = weights.split_last().expect("neurons always have a bias");
+    bias + weights.iter().zip(inputs).map(|(w, x)| w * x).sum::<f32>()
+}
+
+/// Let a [`NeuralPolicy`] resource play, when there is one
+pub struct PolicyPlugin;
+
+impl Plugin for PolicyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            drive_with_policy
+                .in_set(SimulationSystems::Input)
+                .before(apply_agent_input)
+                .run_if(resource_exists::<NeuralPolicy>),
+        );
+    }
+}
+
+fn drive_with_policy(
+    policy: Res<NeuralPolicy>,
+    mut input: ResMut<AgentInput>,
+    dinos: Query<(&Dino, &Transform, &Sprite), Without<Ghost>>,
+    obstacles: Query<(&Transform, &Sprite), With<Obstacle>>,
+    status: Res<GameStatus>,
+    config: Res<GameConfig>,
+) {
+    if let Some(observation) = observe(&dinos, &obstacles, &status, &config) {
+        input.0 = policy.act(&observation);
+    }
+}
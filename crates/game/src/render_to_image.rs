@@ -0,0 +1,133 @@
+use std::{path::PathBuf, time::Duration};
+
+use bevy::{
+    camera::RenderTarget,
+    prelude::*,
+    render::{
+        render_resource::{TextureFormat, TextureUsages},
+        view::screenshot::{save_to_disk, Screenshot, ScreenshotCaptured},
+    },
+    time::TimeUpdateStrategy,
+};
+use log::debug;
+
+use crate::{GameScreen, GameStatus, RunSeed};
+
+/// Settings for [`AppType::RenderToImageTesting`](crate::app::AppType::RenderToImageTesting)
+#[derive(Debug, Clone, Resource)]
+pub struct RenderToImageConfig {
+    /// Width of the offscreen render target in pixels
+    pub width: u32,
+    /// Height of the offscreen render target in pixels
+    pub height: u32,
+    /// Frames each screen is held for, the capture is requested after the last one
+    pub frames: u32,
+    /// Screens the game walks through in order before rendering
+    pub screens: Vec<GameScreen>,
+    /// Path of the PNG written once the frame is captured
+    pub output: PathBuf,
+}
+
+impl Default for RenderToImageConfig {
+    fn default() -> Self {
+        Self {
+            width: 1280,
+            height: 720,
+            frames: 10,
+            screens: vec![GameScreen::StartScreen],
+            output: PathBuf::from("render_to_image.png"),
+        }
+    }
+}
+
+/// Handle of the image the offscreen camera renders into
+#[derive(Resource, Deref)]
+pub struct RenderTargetImage(pub Handle<Image>);
+
+/// Frame time used while rendering to an image, so captures don't depend on the machine speed
+const RENDER_TO_IMAGE_FRAME_TIME: Duration = Duration::from_nanos(16_666_667);
+
+/// Seed of every captured run, so the obstacles on screen are the same on every capture
+const RENDER_TO_IMAGE_SEED: u64 = 0;
+
+/// Render the game into an image, capture one frame to disk and exit
+pub struct RenderToImagePlugin;
+
+impl Plugin for RenderToImagePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RenderToImageConfig>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(
+                RENDER_TO_IMAGE_FRAME_TIME,
+            ))
+            .insert_resource(RunSeed(Some(RENDER_TO_IMAGE_SEED)))
+            .add_systems(Startup, setup_render_to_image_camera)
+            .add_systems(Update, walk_screens_then_capture);
+    }
+}
+
+fn setup_render_to_image_camera(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut game_status: ResMut<GameStatus>,
+    mut next_screen: ResMut<NextState<GameScreen>>,
+    config: Res<RenderToImageConfig>,
+) {
+    debug!(
+        "Setup render to image camera with {}x{} target",
+        config.width, config.height
+    );
+    let mut image = Image::new_target_texture(
+        config.width,
+        config.height,
+        TextureFormat::bevy_default(),
+        None,
+    );
+    // The screenshot copies out of the target texture
+    image.texture_descriptor.usage |= TextureUsages::COPY_SRC;
+    let handle = images.add(image);
+
+    commands.spawn((
+        Camera2d,
+        RenderTarget::Image(handle.clone().into()),
+        // Without a window there is no primary camera for UI to fall back to
+        IsDefaultUiCamera,
+    ));
+    commands.insert_resource(RenderTargetImage(handle));
+
+    game_status.window_width = config.width as f32;
+    game_status.window_height = config.height as f32;
+    if let Some(screen) = config.screens.first() {
+        next_screen.set(*screen);
+    }
+}
+
+fn walk_screens_then_capture(
+    mut commands: Commands,
+    mut frames: Local<u32>,
+    config: Res<RenderToImageConfig>,
+    target: Res<RenderTargetImage>,
+    mut next_screen: ResMut<NextState<GameScreen>>,
+) {
+    *frames += 1;
+    let frames_per_screen = config.frames.max(1);
+    if !frames.is_multiple_of(frames_per_screen) {
+        return;
+    }
+    let visited = (*frames / frames_per_screen) as usize;
+    if let Some(screen) = config.screens.get(visited) {
+        next_screen.set(*screen);
+        return;
+    }
+    if visited != config.screens.len().max(1) {
+        return;
+    }
+    info!("Capturing frame {} to {}", *frames, config.output.display());
+    commands
+        .spawn(Screenshot::image(target.0.clone()))
+        .observe(save_to_disk(config.output.clone()))
+        .observe(exit_after_capture);
+}
+
+fn exit_after_capture(_captured: On<ScreenshotCaptured>, mut exit: MessageWriter<AppExit>) {
+    exit.write(AppExit::Success);
+}
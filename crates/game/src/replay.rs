@@ -0,0 +1,340 @@
+//! Record the jump and duck inputs of a run, and play them back through the same systems.
+//! Together with the seed, the config and the window width a replay reproduces the run exactly.
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rng::reseed_game_rng, storage, ActionState, FixedActionState, GameAction, GameConfig, GameRng,
+    GameScreen, GameStatus, InGame, SimulationSystems,
+};
+
+/// Actions a replay records, everything else stays live during playback
+const REPLAYED_ACTIONS: [GameAction; 2] = [GameAction::Jump, GameAction::Duck];
+
+/// A replay file: everything needed to play a run again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    /// Format version, files from another version are rejected
+    pub version: u32,
+    pub seed: u64,
+    /// Config the run was played with
+    pub config: GameConfig,
+    /// Logical window width the run was played at, obstacle speed and spawn edges depend on it
+    pub window_width: f32,
+    /// Actions already held when the run started
+    pub held_at_start: Vec<GameAction>,
+    /// Every press and release, in tick order
+    pub events: Vec<ReplayEvent>,
+    /// Simulation steps the run lasted
+    pub ticks: u64,
+    /// Final score, zero while the run is still going
+    #[serde(default)]
+    pub score: u64,
+}
+
+/// An action pressed or released at a simulation step, counted from the start of the run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub tick: u64,
+    pub action: GameAction,
+    pub pressed: bool,
+}
+
+impl Replay {
+    /// Current replay format version
+    pub const VERSION: u32 = 2;
+
+    /// Storage key of the last finished run
+    pub const LAST_RUN_KEY: &'static str = "last_replay.json";
+
+    /// Storage key of the best run so far
+    pub const BEST_RUN_KEY: &'static str = "best_replay.json";
+
+    /// Read the replay saved under `key`, `None` if there is none or it can't be read
+    fn load_from_storage(key: &str) -> Option<Self> {
+        let json = storage::load(key)?;
+        Self::from_json(&json)
+            .inspect_err(|e| warn!("Ignoring replay {}: {}", key, e))
+            .ok()
+    }
+
+    fn save_to_storage(&self, key: &str) -> Result<(), std::io::Error> {
+        let json = self
+            .to_json()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        storage::save(key, &json)
+    }
+
+    pub fn new(seed: u64, config: GameConfig, window_width: f32) -> Self {
+        Self {
+            version: Self::VERSION,
+            seed,
+            config,
+            window_width,
+            held_at_start: Vec::new(),
+            events: Vec::new(),
+            ticks: 0,
+            score: 0,
+        }
+    }
+
+    /// Load a replay from a JSON string, fails on other format versions
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let replay: Self = serde_json::from_str(json)?;
+        if replay.version != Self::VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported replay version {}, expected {}",
+                replay.version,
+                Self::VERSION
+            )));
+        }
+        Ok(replay)
+    }
+
+    /// Export the replay to a JSON string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Load a replay file shared by a player
+    pub fn load_from_file(path: impl AsRef<std::path::Path>) -> Result<Self, std::io::Error> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json(&json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    /// Save the replay to a file
+    pub fn save_to_file(&self, path: impl AsRef<std::path::Path>) -> Result<(), std::io::Error> {
+        let json = self
+            .to_json()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Records the current run, restarted with every run
+#[derive(Debug, Resource)]
+pub struct ReplayRecorder {
+    replay: Replay,
+    held: HashSet<GameAction>,
+}
+
+impl ReplayRecorder {
+    /// Run recorded so far
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+}
+
+/// Highest scoring run so far, the one the ghost replays
+#[derive(Debug, Default, Resource)]
+pub struct BestReplay(pub Option<Replay>);
+
+/// Plays a replay instead of the live jump and duck inputs, on every run
+#[derive(Debug, Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    next_event: usize,
+    held: HashSet<GameAction>,
+    tick: u64,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        let mut playback = Self {
+            replay,
+            next_event: 0,
+            held: HashSet::new(),
+            tick: 0,
+        };
+        playback.rewind();
+        playback
+    }
+
+    /// Go back to the start of the replay
+    fn rewind(&mut self) {
+        self.next_event = 0;
+        self.held = self.replay.held_at_start.iter().copied().collect();
+        self.tick = 0;
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Every recorded tick has been played
+    pub fn finished(&self) -> bool {
+        self.tick >= self.replay.ticks
+    }
+
+    /// Replace the jump and duck actions with the ones recorded for the next tick
+    pub(crate) fn step(&mut self, actions: &mut ActionState) {
+        let held_before = self.held.clone();
+        while let Some(event) = self.replay.events.get(self.next_event) {
+            if event.tick > self.tick {
+                break;
+            }
+            if event.pressed {
+                self.held.insert(event.action);
+            } else {
+                self.held.remove(&event.action);
+            }
+            self.next_event += 1;
+        }
+        for action in REPLAYED_ACTIONS {
+            actions.set(
+                action,
+                self.held.contains(&action),
+                held_before.contains(&action),
+            );
+        }
+        self.tick += 1;
+    }
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BestReplay>()
+            .add_systems(
+                OnEnter(InGame),
+                (
+                    pin_run_width,
+                    start_recording.after(reseed_game_rng).after(pin_run_width),
+                    restart_playback.run_if(resource_exists::<ReplayPlayback>),
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    play_back_actions.run_if(resource_exists::<ReplayPlayback>),
+                    record_actions.run_if(not(resource_exists::<ReplayPlayback>)),
+                )
+                    .in_set(SimulationSystems::Input),
+            )
+            .add_systems(
+                OnEnter(GameScreen::GameOverScreen),
+                finish_recording.run_if(not(resource_exists::<ReplayPlayback>)),
+            );
+    }
+}
+
+/// Keep the last and the best finished runs across sessions
+pub struct LastReplayPlugin;
+
+impl Plugin for LastReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(BestReplay(Replay::load_from_storage(Replay::BEST_RUN_KEY)))
+            .add_systems(
+                OnEnter(GameScreen::GameOverScreen),
+                save_replays
+                    .after(finish_recording)
+                    .run_if(not(resource_exists::<ReplayPlayback>)),
+            );
+    }
+}
+
+/// A played back or raced run goes at the width it was recorded at, whatever the window is now
+fn pin_run_width(
+    playback: Option<Res<ReplayPlayback>>,
+    config: Res<GameConfig>,
+    best: Res<BestReplay>,
+    mut status: ResMut<GameStatus>,
+) {
+    let replay = playback
+        .as_deref()
+        .map(ReplayPlayback::replay)
+        .or_else(|| best.0.as_ref().filter(|_| config.ghost));
+    if let Some(replay) = replay {
+        status.window_width = replay.window_width;
+    }
+}
+
+fn start_recording(
+    mut commands: Commands,
+    rng: Res<GameRng>,
+    config: Res<GameConfig>,
+    status: Res<GameStatus>,
+) {
+    commands.insert_resource(ReplayRecorder {
+        replay: Replay::new(rng.seed(), config.clone(), status.window_width),
+        held: HashSet::new(),
+    });
+}
+
+pub(crate) fn record_actions(
+    actions: Res<FixedActionState>,
+    recorder: Option<ResMut<ReplayRecorder>>,
+) {
+    let Some(mut recorder) = recorder else {
+        return;
+    };
+    let recorder = recorder.as_mut();
+    let tick = recorder.replay.ticks;
+    if tick == 0 {
+        for action in REPLAYED_ACTIONS {
+            if actions.was_pressed(action) {
+                recorder.replay.held_at_start.push(action);
+                recorder.held.insert(action);
+            }
+        }
+    }
+    for action in REPLAYED_ACTIONS {
+        let pressed = actions.pressed(action);
+        if pressed != recorder.held.contains(&action) {
+            recorder.replay.events.push(ReplayEvent {
+                tick,
+                action,
+                pressed,
+            });
+            if pressed {
+                recorder.held.insert(action);
+            } else {
+                recorder.held.remove(&action);
+            }
+        }
+    }
+    recorder.replay.ticks += 1;
+}
+
+fn restart_playback(mut playback: ResMut<ReplayPlayback>) {
+    playback.rewind();
+}
+
+/// Drive the jump and duck actions from the replay
+fn play_back_actions(mut playback: ResMut<ReplayPlayback>, mut actions: ResMut<FixedActionState>) {
+    playback.step(&mut actions);
+}
+
+/// Stamp the final score on the recorded run, it becomes the best run if it beats it
+fn finish_recording(
+    status: Res<GameStatus>,
+    recorder: Option<ResMut<ReplayRecorder>>,
+    mut best: ResMut<BestReplay>,
+) {
+    let Some(mut recorder) = recorder else {
+        return;
+    };
+    recorder.replay.score = status.score;
+    let best_score = best.0.as_ref().map_or(0, |replay| replay.score);
+    if status.score > best_score {
+        best.0 = Some(recorder.replay.clone());
+    }
+}
+
+fn save_replays(recorder: Option<Res<ReplayRecorder>>, best: Res<BestReplay>) {
+    if let Some(recorder) = recorder {
+        if let Err(e) = recorder.replay.save_to_storage(Replay::LAST_RUN_KEY) {
+            warn!("Failed to save the last replay: {}", e);
+        }
+    }
+    if let Some(replay) = best.0.as_ref().filter(|_| best.is_changed()) {
+        if let Err(e) = replay.save_to_storage(Replay::BEST_RUN_KEY) {
+            warn!("Failed to save the best replay: {}", e);
+        }
+    }
+}
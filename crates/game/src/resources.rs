@@ -1,10 +1,17 @@
+use std::path::{Path, PathBuf};
+
 use bevy::prelude::*;
 use bevy_kira_audio::AudioSource;
 use serde::{Deserialize, Serialize};
 
+use crate::{components::Hitbox, InputBindings};
+
 /// Golden ratio constant used for proportional sizing
 const GOLDEN_RATIO: f32 = 0.618;
 
+/// Speed every run starts at
+pub const INITIAL_GAME_SPEED: u64 = 5;
+
 #[derive(Debug, Default, Resource)]
 pub struct GameStatus {
     pub score: u64,
@@ -19,26 +26,83 @@ pub struct SpeedControlInfo {
     pub max_game_speed: u64,
 }
 
+impl SpeedControlInfo {
+    /// Speed ramp every run starts with
+    pub const INITIAL: Self = Self {
+        speed_increment: 100,
+        max_game_speed: u64::MAX,
+    };
+}
+
+/// Best score reached since the game was started
+#[derive(Debug, Default, Resource)]
+pub struct SessionHighScore(pub u64);
+
+/// What the current run has achieved so far, the score is derived from it
+#[derive(Debug, Default, Resource)]
+pub struct RunProgress {
+    /// Distance travelled in pixels
+    pub distance: f32,
+    /// Obstacles that made it past the dino
+    pub obstacles_cleared: u64,
+    /// Unrounded score, `GameStatus::score` is its integer part
+    pub points: f64,
+    /// Highest `GameStatus::speed` reached
+    pub max_speed: u64,
+    /// Played time in seconds, pauses excluded
+    pub duration_secs: f32,
+}
+
 #[derive(Resource, Deref)]
 pub struct DinoJumpMusic(pub Handle<AudioSource>);
 
-/// Configuration for game entities that can be modified via egui and exported/imported
+/// Configuration for game entities that can be modified via egui and exported/imported.
+/// Fields missing from an older config file keep their default value.
 #[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameConfig {
     /// Dino width in pixels
     pub dino_width: f32,
     /// Dino height in pixels
     pub dino_height: f32,
-    /// Dino jump height in pixels
-    pub dino_jump_height: f32,
+    /// Dino height in pixels while ducking
+    pub dino_duck_height: f32,
+    /// Upward speed a jump starts with, in pixels per second
+    pub dino_jump_velocity: f32,
+    /// Downward acceleration once the jump is released, in pixels per second squared
+    pub dino_gravity: f32,
+    /// How long holding the jump keeps the dino rising without gravity, in seconds
+    pub dino_max_jump_hold: f32,
+    /// How long a jump pressed too early is kept until the dino can jump, in seconds
+    pub jump_buffer_secs: f32,
+    /// How long after leaving the ground without jumping the dino can still jump, in seconds
+    pub coyote_time_secs: f32,
     /// Dino X position offset (0.0 to 1.0, percentage of window width from left)
     pub dino_x_offset: f32,
     /// Tree width in pixels
     pub tree_width: f32,
     /// Tree height in pixels
     pub tree_height: f32,
+    /// Bird width in pixels
+    pub bird_width: f32,
+    /// Bird height in pixels
+    pub bird_height: f32,
+    /// Birds only show up once `GameStatus::speed` reaches this
+    pub bird_unlock_speed: u64,
+    /// Standing dino hitboxes: head, body and legs
+    pub dino_hitboxes: Vec<Hitbox>,
+    /// Ducking dino hitboxes: head and body
+    pub dino_duck_hitboxes: Vec<Hitbox>,
+    /// Tree hitboxes: trunk and branches
+    pub tree_hitboxes: Vec<Hitbox>,
+    /// Bird hitboxes: body and head, the wings don't count
+    pub bird_hitboxes: Vec<Hitbox>,
     /// Ground Y position (0.0 is center of screen)
     pub ground_y_pos: f32,
+    /// Seed every run starts from, a random one is picked for each run when unset
+    pub seed: Option<u64>,
+    /// Race a ghost replaying the best run, on the same obstacles
+    pub ghost: bool,
 }
 
 impl Default for GameConfig {
@@ -46,14 +110,45 @@ impl Default for GameConfig {
         // These match the original hardcoded constants
         let dino_width = 50.0;
         let tree_width = 30.0;
+        let bird_width = 46.0;
         Self {
             dino_width,
             dino_height: dino_width / GOLDEN_RATIO, // ~80.9
-            dino_jump_height: dino_width / GOLDEN_RATIO * 2.4, // ~194.2
+            dino_duck_height: dino_width / GOLDEN_RATIO * 0.55, // ~44.5, under middle birds
+            // A tap peaks at 120px after 0.2s, holding adds up to 144px
+            dino_jump_velocity: 1200.0,
+            dino_gravity: 6000.0,
+            dino_max_jump_hold: 0.12,
+            jump_buffer_secs: 0.1,
+            coyote_time_secs: 0.08,
             dino_x_offset: 0.2,
             tree_width,
             tree_height: tree_width / GOLDEN_RATIO, // ~48.5
+            bird_width,
+            bird_height: bird_width * GOLDEN_RATIO, // ~28.4
+            // Reached after clearing four obstacles
+            bird_unlock_speed: 1_000,
+            // Like the original game, corners and edges the eye barely notices don't count
+            dino_hitboxes: vec![
+                Hitbox::new(Vec2::new(0.2, 0.325), Vec2::new(0.6, 0.35)),
+                Hitbox::new(Vec2::new(0.0, -0.025), Vec2::new(1.0, 0.35)),
+                Hitbox::new(Vec2::new(0.0, -0.35), Vec2::new(0.6, 0.3)),
+            ],
+            dino_duck_hitboxes: vec![
+                Hitbox::new(Vec2::new(0.25, 0.2), Vec2::new(0.5, 0.6)),
+                Hitbox::new(Vec2::new(-0.05, -0.2), Vec2::new(0.9, 0.6)),
+            ],
+            tree_hitboxes: vec![
+                Hitbox::new(Vec2::new(0.0, 0.0), Vec2::new(0.5, 1.0)),
+                Hitbox::new(Vec2::new(0.0, 0.1), Vec2::new(1.0, 0.5)),
+            ],
+            bird_hitboxes: vec![
+                Hitbox::new(Vec2::new(0.1, 0.0), Vec2::new(0.8, 0.6)),
+                Hitbox::new(Vec2::new(-0.35, 0.15), Vec2::new(0.3, 0.5)),
+            ],
             ground_y_pos: 0.0,
+            seed: None,
+            ghost: false,
         }
     }
 }
@@ -73,18 +168,115 @@ impl GameConfig {
     }
 
     /// Load config from file, returns default if file doesn't exist or is invalid
-    pub fn load_from_file() -> Self {
-        std::fs::read_to_string(Self::CONFIG_FILE)
+    pub fn load_from_file(path: impl AsRef<Path>) -> Self {
+        std::fs::read_to_string(path)
             .ok()
             .and_then(|content| Self::from_json(&content).ok())
             .unwrap_or_default()
     }
 
     /// Save config to file
-    pub fn save_to_file(&self) -> Result<(), std::io::Error> {
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), std::io::Error> {
         let json = self
             .to_json()
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
-        std::fs::write(Self::CONFIG_FILE, json)
+        std::fs::write(path, json)
+    }
+}
+
+/// Where the [`GameConfig`] is loaded from and exported to,
+/// [`InputBindings`] are kept in the same directory
+#[derive(Debug, Clone, PartialEq, Eq, Resource)]
+pub struct ConfigPath(pub PathBuf);
+
+impl Default for ConfigPath {
+    fn default() -> Self {
+        Self(PathBuf::from(GameConfig::CONFIG_FILE))
+    }
+}
+
+impl ConfigPath {
+    /// The input bindings file next to the config file
+    pub fn bindings_file(&self) -> PathBuf {
+        self.0.with_file_name(InputBindings::BINDINGS_FILE)
+    }
+}
+
+/// One finished run kept in the high score table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub score: u64,
+    /// Highest `GameStatus::speed` reached during the run
+    pub max_speed: u64,
+    /// Played time in seconds, pauses excluded
+    pub duration_secs: f32,
+    /// When the run ended, in seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+impl HighScoreEntry {
+    /// Date the run ended on, formatted as `YYYY-MM-DD` (UTC)
+    pub fn date(&self) -> String {
+        // Days to civil date, from Howard Hinnant's `civil_from_days`
+        let z = (self.timestamp / 86_400) as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+
+/// Best runs across sessions, highest score first, persisted with [`crate::storage`]
+#[derive(Debug, Clone, Default, Resource, Serialize, Deserialize)]
+pub struct HighScores {
+    pub entries: Vec<HighScoreEntry>,
+}
+
+impl HighScores {
+    /// Storage key, a file name on native and a `localStorage` key on the web
+    pub const STORAGE_KEY: &'static str = "high_scores.json";
+
+    /// Runs kept in the table
+    pub const MAX_ENTRIES: usize = 10;
+
+    /// Load high scores from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Export high scores to a JSON string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Load saved high scores, returns an empty table if none are saved or they are invalid
+    pub fn load() -> Self {
+        crate::storage::load(Self::STORAGE_KEY)
+            .and_then(|content| Self::from_json(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save high scores
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        let json = self
+            .to_json()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        crate::storage::save(Self::STORAGE_KEY, &json)
+    }
+
+    /// Add a finished run, returns its rank (0 is best) if it made it into the table
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        let rank = self.entries.partition_point(|e| e.score >= entry.score);
+        if rank >= Self::MAX_ENTRIES {
+            return None;
+        }
+        self.entries.insert(rank, entry);
+        self.entries.truncate(Self::MAX_ENTRIES);
+        Some(rank)
     }
 }
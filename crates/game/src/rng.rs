@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+use crate::{ghost::ghost_seed, BestReplay, GameConfig, InGame};
+
+/// Source of every random choice made during a run.
+/// A run started from the same seed with the same inputs plays out exactly the same.
+#[derive(Resource, Debug, Clone)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seed the current run was started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Seed given on the command line, takes precedence over [`GameConfig::seed`]
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct RunSeed(pub Option<u64>);
+
+pub struct GameRngPlugin;
+
+impl Plugin for GameRngPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSeed>()
+            .insert_resource(GameRng::new(rand::random()))
+            .add_systems(OnEnter(InGame), reseed_game_rng);
+    }
+}
+
+/// Every run starts from the fixed seed if there is one, a fresh random seed otherwise.
+/// Racing a ghost replays its obstacles, unless a seed was given on the command line.
+pub(crate) fn reseed_game_rng(
+    run_seed: Res<RunSeed>,
+    config: Res<GameConfig>,
+    best: Option<Res<BestReplay>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let seed = run_seed
+        .0
+        .or_else(|| ghost_seed(&config, best.as_deref()))
+        .or(config.seed)
+        .unwrap_or_else(rand::random);
+    info!("Starting run with seed {}", seed);
+    *rng = GameRng::new(seed);
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::{
+    obstacle::world_speed, GameConfig, GameScreen, GameStatus, InGame, ObstacleCleared,
+    RunProgress, SessionHighScore, SimulationSystems, SpeedControlInfo, INITIAL_GAME_SPEED,
+};
+
+/// Distance in pixels worth one point before the speed multiplier
+const PIXELS_PER_POINT: f64 = 64.0;
+
+/// Points for every obstacle that made it past the dino, before the speed multiplier
+const POINTS_PER_OBSTACLE: f64 = 10.0;
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunProgress>()
+            .init_resource::<SessionHighScore>()
+            .add_systems(OnEnter(InGame), start_new_run)
+            .add_systems(
+                OnEnter(GameScreen::GameOverScreen),
+                update_session_high_score,
+            )
+            // Only counting while playing freezes the score on pause and game over
+            .add_systems(
+                FixedUpdate,
+                (score_distance, score_cleared_obstacles, track_run_stats)
+                    .chain()
+                    .in_set(SimulationSystems::Score),
+            );
+    }
+}
+
+/// Faster runs are worth more, the speed grows exponentially so the bonus follows its log
+fn speed_multiplier(speed: u64) -> f64 {
+    1.0 + (speed.max(1) as f64).log2() / 10.0
+}
+
+fn start_new_run(
+    mut status: ResMut<GameStatus>,
+    mut speed_control_info: ResMut<SpeedControlInfo>,
+    mut progress: ResMut<RunProgress>,
+) {
+    status.score = 0;
+    status.speed = INITIAL_GAME_SPEED;
+    *speed_control_info = SpeedControlInfo::INITIAL;
+    *progress = RunProgress::default();
+}
+
+fn score_distance(
+    time: Res<Time>,
+    mut status: ResMut<GameStatus>,
+    mut progress: ResMut<RunProgress>,
+    config: Res<GameConfig>,
+) {
+    let travelled = time.delta_secs() * world_speed(&status, config.tree_width);
+    progress.distance += travelled;
+    progress.points += travelled as f64 / PIXELS_PER_POINT * speed_multiplier(status.speed);
+    status.score = progress.points as u64;
+}
+
+fn score_cleared_obstacles(
+    mut cleared: MessageReader<ObstacleCleared>,
+    mut status: ResMut<GameStatus>,
+    mut progress: ResMut<RunProgress>,
+) {
+    for _ in cleared.read() {
+        progress.obstacles_cleared += 1;
+        progress.points += POINTS_PER_OBSTACLE * speed_multiplier(status.speed);
+    }
+    status.score = progress.points as u64;
+}
+
+fn track_run_stats(time: Res<Time>, status: Res<GameStatus>, mut progress: ResMut<RunProgress>) {
+    progress.duration_secs += time.delta_secs();
+    progress.max_speed = progress.max_speed.max(status.speed);
+}
+
+fn update_session_high_score(status: Res<GameStatus>, mut high_score: ResMut<SessionHighScore>) {
+    high_score.0 = high_score.0.max(status.score);
+}
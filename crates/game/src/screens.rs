@@ -1,4 +1,7 @@
-use bevy::state::state::States;
+use bevy::{
+    ecs::system::ResMut,
+    state::state::{ComputedStates, NextState, States},
+};
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, States)]
 pub enum GameScreen {
@@ -15,3 +18,28 @@ pub enum GameScreen {
     // End State
     ExitScreen,
 }
+
+/// Active while a run is in progress, including while it is paused,
+/// so pausing and resuming keeps the entities of the run alive
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = GameScreen;
+
+    const ALLOW_SAME_STATE_TRANSITIONS: bool = false;
+
+    fn compute(screen: GameScreen) -> Option<Self> {
+        match screen {
+            GameScreen::PlayScreen
+            | GameScreen::UnfocusedPauseScreen
+            | GameScreen::ManuallyPauseScreen => Some(InGame),
+            GameScreen::StartScreen | GameScreen::GameOverScreen | GameScreen::ExitScreen => None,
+        }
+    }
+}
+
+/// Start a run right away, for players that don't press anything to begin
+pub(crate) fn start_playing(mut next_screen: ResMut<NextState<GameScreen>>) {
+    next_screen.set(GameScreen::PlayScreen);
+}
@@ -0,0 +1,64 @@
+//! The game simulation steps on `FixedUpdate`, one tick at a time in a fixed order,
+//! so a run only depends on its seed and the actions held at each tick.
+
+use bevy::{app::FixedMain, prelude::*};
+
+use crate::{GameScreen, InGame};
+
+/// Order of a simulation tick, only stepped while playing or by hand
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SimulationSystems {
+    /// Decide the actions held this tick, live or replayed
+    Input,
+    /// Move the dinos and the obstacles
+    Move,
+    /// Check the dinos against the obstacles
+    Collide,
+    /// Count what the tick was worth
+    Score,
+}
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            FixedUpdate,
+            (
+                SimulationSystems::Input,
+                SimulationSystems::Move,
+                SimulationSystems::Collide,
+                SimulationSystems::Score,
+            )
+                .chain()
+                .run_if(
+                    in_state(GameScreen::PlayScreen)
+                        .or(in_state(InGame).and(resource_exists::<ManualStep>))
+                        .and(no_screen_change_pending),
+                ),
+        );
+    }
+}
+
+/// Once a tick ended the run, later ticks of the same frame must not keep playing it
+fn no_screen_change_pending(next_screen: Res<NextState<GameScreen>>) -> bool {
+    matches!(*next_screen, NextState::Unchanged)
+}
+
+/// Present while [`step_one_tick`] runs, so the tick also plays on a pause screen
+#[derive(Resource)]
+struct ManualStep;
+
+/// Run one simulation tick by hand, also while the run is paused. The fixed clock advances
+/// by one timestep, the same way Bevy does when enough virtual time went by.
+pub fn step_one_tick(world: &mut World) {
+    world.insert_resource(ManualStep);
+    let timestep = world.resource::<Time<Fixed>>().timestep();
+    world.resource_mut::<Time<Fixed>>().advance_by(timestep);
+    let fixed = world.resource::<Time<Fixed>>().as_generic();
+    *world.resource_mut::<Time>() = fixed;
+    world.run_schedule(FixedMain);
+    let virtual_time = world.resource::<Time<Virtual>>().as_generic();
+    *world.resource_mut::<Time>() = virtual_time;
+    world.remove_resource::<ManualStep>();
+}
@@ -0,0 +1,67 @@
+//! Small key-value persistence: files in the platform data directory on native,
+//! `localStorage` on the web where there is no file system.
+
+/// Directory under the platform data directory holding the saved files
+#[cfg(not(target_arch = "wasm32"))]
+const DATA_DIR_NAME: &str = "dinosaur-game";
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path_for(key: &str) -> std::path::PathBuf {
+    dirs::data_dir()
+        .map(|dir| dir.join(DATA_DIR_NAME))
+        .unwrap_or_default()
+        .join(key)
+}
+
+/// Read the value saved under `key`, `None` if it was never saved or can't be read
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load(key: &str) -> Option<String> {
+    std::fs::read_to_string(path_for(key)).ok()
+}
+
+/// Save `value` under `key`, replacing what was there
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(key: &str, value: &str) -> Result<(), std::io::Error> {
+    let path = path_for(key);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, value)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// Read the value saved under `key`, `None` if it was never saved or can't be read
+#[cfg(target_arch = "wasm32")]
+pub fn load(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+/// Save `value` under `key`, replacing what was there
+#[cfg(target_arch = "wasm32")]
+pub fn save(key: &str, value: &str) -> Result<(), std::io::Error> {
+    let storage = local_storage().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::Unsupported, "localStorage unavailable")
+    })?;
+    storage
+        .set_item(key, value)
+        .map_err(|e| std::io::Error::other(format!("{e:?}")))
+}
+
+/// Seconds since the unix epoch
+#[cfg(not(target_arch = "wasm32"))]
+pub fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Seconds since the unix epoch, `SystemTime` is not available on the web
+#[cfg(target_arch = "wasm32")]
+pub fn unix_timestamp() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
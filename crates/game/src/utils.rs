@@ -7,6 +7,11 @@ pub fn cleanup_component<C: Component>(queries: Query<Entity, With<C>>, mut comm
     }
 }
 
+/// Whether egui wanted the pointer on the last frame, kept in sync by the debug plugin.
+/// Gameplay systems read this instead of egui so they still run when egui is not added.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct EguiWantsPointer(pub bool);
+
 /// Check if egui wants pointer input (e.g., clicking on debug window)
 /// Returns true if egui wants to capture the pointer, false otherwise
 pub fn egui_wants_pointer(contexts: &mut EguiContexts) -> bool {
@@ -0,0 +1,248 @@
+//! Evolve a [`NeuralPolicy`] against the real game, running one headless game per thread.
+//! The best network is written to disk, the game plays it with `--policy <file>`.
+
+use std::{num::NonZeroUsize, thread};
+
+use dinosaur_game::{DinoEnv, NeuralPolicy};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Neurons in the hidden layer
+const HIDDEN: usize = 8;
+/// Longest an evaluation run goes on, about five minutes of play
+const MAX_TICKS: u32 = 20_000;
+/// Individuals copied unchanged into the next generation
+const ELITES: usize = 4;
+/// Individuals compared when picking a parent
+const TOURNAMENT: usize = 3;
+/// Chance for each weight of a child to be nudged
+const MUTATION_RATE: f64 = 0.1;
+/// Typical size of a nudge
+const MUTATION_SCALE: f32 = 0.3;
+
+/// What `--help` prints
+const HELP: &str = "\
+Evolve a network playing the dinosaur game
+
+Usage: dino-trainer [OPTIONS]
+
+Options:
+  --generations <NUMBER>    Generations to evolve [default: 50]
+  --population <NUMBER>     Networks in every generation [default: 64]
+  --episodes <NUMBER>       Runs each network plays per generation [default: 3]
+  --seed <NUMBER>           Seed of the random first generation [default: 0]
+  --out <FILE>              Where the best network is saved [default: best_policy.json]
+  -h, --help                Print this help
+";
+
+/// How a training session is set up, read from the command line
+struct Settings {
+    help: bool,
+    generations: u32,
+    population: usize,
+    /// Runs each individual plays per generation, on the same seeds for everyone
+    episodes: u64,
+    seed: u64,
+    out: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            help: false,
+            generations: 50,
+            population: 64,
+            episodes: 3,
+            seed: 0,
+            out: "best_policy.json".to_string(),
+        }
+    }
+}
+
+/// Parse the value given with `name` as a number
+fn number<T: std::str::FromStr>(name: &str, value: String) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {} value {:?}", name, value))
+}
+
+impl Settings {
+    /// Parse the arguments after the program name, flags take their value as
+    /// `--<name> <value>` or `--<name>=<value>`
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut settings = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => {
+                    (name.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| format!("{} needs a value", name))
+            };
+            match name.as_str() {
+                "-h" | "--help" => match inline_value {
+                    Some(_) => return Err(format!("{} takes no value", name)),
+                    None => settings.help = true,
+                },
+                "--generations" => settings.generations = number(&name, value()?)?,
+                "--population" => settings.population = number(&name, value()?)?,
+                "--episodes" => settings.episodes = number(&name, value()?)?,
+                "--seed" => settings.seed = number(&name, value()?)?,
+                "--out" => settings.out = value()?,
+                _ => return Err(format!("Unknown argument {:?}", name)),
+            }
+        }
+        // Keep room for at least one child, and score everyone on at least one run
+        settings.population = settings.population.max(ELITES + 1);
+        settings.episodes = settings.episodes.max(1);
+        Ok(settings)
+    }
+}
+
+/// Roughly normal noise, from two uniform samples (Box-Muller)
+fn gaussian(rng: &mut impl Rng) -> f32 {
+    let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
+}
+
+fn random_policy(rng: &mut impl Rng) -> NeuralPolicy {
+    let mut policy = NeuralPolicy::zeroed(HIDDEN);
+    for weight in policy.weights.iter_mut() {
+        *weight = gaussian(rng);
+    }
+    policy
+}
+
+/// Mean score of `policy` over the runs started from `seeds`
+fn fitness(env: &mut DinoEnv, policy: &NeuralPolicy, seeds: &[u64]) -> f64 {
+    let total: u64 = seeds
+        .iter()
+        .map(|&seed| {
+            let mut observation = env.reset(seed);
+            for _ in 0..MAX_TICKS {
+                let (next, _, done) = env.step(policy.act(&observation));
+                if done {
+                    break;
+                }
+                observation = next;
+            }
+            env.score()
+        })
+        .sum();
+    total as f64 / seeds.len() as f64
+}
+
+/// Score every individual, each thread playing its share in its own game
+fn evaluate(population: &[NeuralPolicy], seeds: &[u64]) -> Vec<f64> {
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk = population.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let workers: Vec<_> = population
+            .chunks(chunk)
+            .map(|individuals| {
+                scope.spawn(move || {
+                    let mut env = DinoEnv::default();
+                    individuals
+                        .iter()
+                        .map(|policy| fitness(&mut env, policy, seeds))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("evaluation thread panicked"))
+            .collect()
+    })
+}
+
+/// Best of a few random individuals
+fn tournament<'a>(
+    population: &'a [NeuralPolicy],
+    scores: &[f64],
+    rng: &mut impl Rng,
+) -> &'a NeuralPolicy {
+    let best = (0..TOURNAMENT)
+        .map(|_| rng.gen_range(0..population.len()))
+        .max_by(|&a, &b| scores[a].total_cmp(&scores[b]))
+        .expect("tournaments are never empty");
+    &population[best]
+}
+
+/// Uniform crossover of two parents, then a few nudged weights
+fn child(a: &NeuralPolicy, b: &NeuralPolicy, rng: &mut impl Rng) -> NeuralPolicy {
+    let mut child = a.clone();
+    for (weight, other) in child.weights.iter_mut().zip(&b.weights) {
+        if rng.gen_bool(0.5) {
+            *weight = *other;
+        }
+        if rng.gen_bool(MUTATION_RATE) {
+            *weight += gaussian(rng) * MUTATION_SCALE;
+        }
+    }
+    child
+}
+
+fn main() {
+    let settings = match Settings::parse(std::env::args().skip(1)) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, HELP);
+            std::process::exit(2);
+        }
+    };
+    if settings.help {
+        print!("{}", HELP);
+        return;
+    }
+    let mut rng = StdRng::seed_from_u64(settings.seed);
+    let mut population: Vec<NeuralPolicy> = (0..settings.population)
+        .map(|_| random_policy(&mut rng))
+        .collect();
+    let mut best_score = f64::NEG_INFINITY;
+
+    for generation in 0..settings.generations {
+        // Fresh runs every generation, so nobody gets good at a single obstacle course
+        let first_seed = u64::from(generation) * settings.episodes;
+        let seeds: Vec<u64> = (first_seed..first_seed + settings.episodes).collect();
+        let scores = evaluate(&population, &seeds);
+
+        let mut ranking: Vec<usize> = (0..population.len()).collect();
+        ranking.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+        let leader = ranking[0];
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        println!(
+            "generation {:>4}: best {:>8.1}  mean {:>8.1}",
+            generation, scores[leader], mean
+        );
+        if scores[leader] > best_score {
+            best_score = scores[leader];
+            if let Err(e) = population[leader].save_to_file(&settings.out) {
+                eprintln!("Failed to save the best policy to {}: {}", settings.out, e);
+            }
+        }
+
+        let mut next: Vec<NeuralPolicy> = ranking[..ELITES]
+            .iter()
+            .map(|&i| population[i].clone())
+            .collect();
+        while next.len() < population.len() {
+            let a = tournament(&population, &scores, &mut rng);
+            let b = tournament(&population, &scores, &mut rng);
+            next.push(child(a, b, &mut rng));
+        }
+        population = next;
+    }
+
+    println!(
+        "Best policy scored {:.1}, saved to {}. Watch it with `dinosaur-game --policy {}`",
+        best_score, settings.out, settings.out
+    );
+}
@@ -78,6 +78,11 @@ async function installLinuxDeps() {
   await $`sudo apt-get install -y --no-install-recommends pkg-config libx11-dev libasound2-dev libudev-dev libxcb-render0-dev libxcb-shape0-dev libxcb-xfixes0-dev clang mold libwayland-dev libxkbcommon-dev`;
 }
 
+async function installRenderTestDeps() {
+  // lavapipe provides a software Vulkan adapter for the render to image tests
+  await $`sudo apt-get install -y --no-install-recommends mesa-vulkan-drivers`;
+}
+
 async function installWasmDeps() {
   if (process.platform === "linux" && process.env.CI) {
     await installLinuxDeps();
@@ -295,6 +300,18 @@ async function test() {
     await $`cargo test --workspace`;
 }
 
+async function testRender() {
+    // Render tests need a wgpu adapter, so they are ignored by the plain test run
+    await $`cargo test --workspace -- --include-ignored`;
+}
+
+async function updateGolden() {
+    await $`cargo test -p dinosaur-e2e --test golden_screens -- --include-ignored`.env({
+        ...process.env,
+        DINO_UPDATE_GOLDEN: "1",
+    });
+}
+
 async function clippy() {
     await $`cargo clippy --workspace --all-targets --all-features -- -D warnings`;
 }
@@ -323,6 +340,11 @@ cli.command("install-linux-deps", "Install dependencies")
     await installLinuxDeps();
   });
 
+cli.command("install-render-test-deps", "Install the software GPU used by render tests")
+  .action(async () => {
+    await installRenderTestDeps();
+  });
+
 cli.command("install-wasm-deps", "Install wasm dependencies")
   .action(async () => {
     await installWasmDeps();
@@ -359,6 +381,16 @@ cli.command("test", "Run tests")
         await test();
     });
 
+cli.command("test-render", "Run tests including the ones rendering to images")
+    .action(async () => {
+        await testRender();
+    });
+
+cli.command("update-golden", "Render every screen and rewrite the golden reference images")
+    .action(async () => {
+        await updateGolden();
+    });
+
 cli.command("clippy", "Run clippy")
     .action(async () => {
         await clippy();